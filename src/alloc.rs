@@ -0,0 +1,169 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Heap usage of a piece of code, as counted by the allocator of the `alloc-stats` feature
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(super) struct Stats {
+    pub(super) allocations: u64,
+
+    /// Bytes allocated in total, whether they were freed since or not
+    #[serde(rename = "allocated_bytes")]
+    pub(super) bytes: u64,
+
+    /// Highest number of bytes live at once, on top of what was live beforehand
+    #[serde(rename = "peak_bytes")]
+    pub(super) peak: u64,
+}
+
+fn bytes(n: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", n)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            bytes(self.bytes),
+            bytes(self.peak)
+        )
+    }
+}
+
+/// Whether allocations are counted, with the `alloc-stats` feature
+pub(super) const COUNTED: bool = cfg!(feature = "alloc-stats");
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use super::Stats;
+
+    #[derive(Copy, Clone)]
+    struct Counters {
+        allocations: u64,
+        bytes: u64,
+        live: u64,
+        peak: u64,
+    }
+
+    thread_local! {
+        // Counted per thread so that solvers running in parallel don't add up. The counters are
+        // const initialized and don't need dropping, so using them never allocates
+        static COUNTERS: Cell<Counters> = const {
+            Cell::new(Counters {
+                allocations: 0,
+                bytes: 0,
+                live: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    fn update(f: impl FnOnce(&mut Counters)) {
+        // Allocations made while the thread is torn down are not counted
+        let _ = COUNTERS.try_with(|c| {
+            let mut counters = c.get();
+            f(&mut counters);
+            c.set(counters);
+        });
+    }
+
+    fn allocated(size: usize) {
+        update(|c| {
+            c.allocations += 1;
+            c.bytes += size as u64;
+            c.live += size as u64;
+            c.peak = c.peak.max(c.live);
+        });
+    }
+
+    fn freed(size: usize) {
+        update(|c| c.live = c.live.saturating_sub(size as u64));
+    }
+
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+        let before = COUNTERS.with(|c| {
+            let mut counters = c.get();
+            counters.peak = counters.live;
+            c.set(counters);
+            counters
+        });
+
+        let result = f();
+
+        let after = COUNTERS.with(Cell::get);
+        let stats = Stats {
+            allocations: after.allocations - before.allocations,
+            bytes: after.bytes - before.bytes,
+            peak: after.peak - before.live,
+        };
+
+        (result, Some(stats))
+    }
+}
+
+/// Runs `f` and returns how much it allocated on the current thread, if allocations are counted
+#[cfg(feature = "alloc-stats")]
+pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    counting::measure(f)
+}
+
+/// Runs `f` and returns how much it allocated on the current thread, if allocations are counted
+#[cfg(not(feature = "alloc-stats"))]
+pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    (f(), None)
+}
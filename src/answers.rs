@@ -0,0 +1,114 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Name of the answers file, relative to the input directory
+pub(super) const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Error)]
+pub(super) enum Error {
+    #[error("failed to read {0:?}")]
+    Read(PathBuf, #[source] io::Error),
+
+    #[error("failed to parse {0:?}")]
+    Parse(PathBuf, #[source] toml::de::Error),
+
+    #[error("failed to write {0:?}")]
+    Write(PathBuf, #[source] io::Error),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Answer {
+    pub(super) year: u16,
+    pub(super) day: usize,
+    pub(super) part: usize,
+
+    /// Name of the input file, relative to the input directory
+    pub(super) input_file: String,
+    pub(super) answer: String,
+}
+
+/// Accepted answers of the real inputs, meant to be committed alongside the solvers
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct Answers {
+    #[serde(default, rename = "answer")]
+    pub(super) answers: Vec<Answer>,
+}
+
+fn file_name(input_file: &Path) -> String {
+    input_file
+        .file_name()
+        .unwrap_or(input_file.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+impl Answers {
+    pub(super) fn path(input_dir: &Path) -> PathBuf {
+        input_dir.join(ANSWERS_FILE)
+    }
+
+    pub(super) fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                toml::from_str(&contents).map_err(|e| Error::Parse(path.to_path_buf(), e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Read(path.to_path_buf(), e)),
+        }
+    }
+
+    pub(super) fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = toml::to_string_pretty(self).expect("answers should be serializable");
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::Write(path.to_path_buf(), e))?;
+        }
+
+        fs::write(path, contents).map_err(|e| Error::Write(path.to_path_buf(), e))
+    }
+
+    pub(super) fn get(
+        &self,
+        year: u16,
+        day: usize,
+        part: usize,
+        input_file: &Path,
+    ) -> Option<&str> {
+        let input_file = file_name(input_file);
+
+        self.answers
+            .iter()
+            .find(|a| (a.year, a.day, a.part, &a.input_file) == (year, day, part, &input_file))
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Records the accepted answer of an input file, replacing the previous one if any
+    pub(super) fn set(
+        &mut self,
+        year: u16,
+        day: usize,
+        part: usize,
+        input_file: &Path,
+        answer: &str,
+    ) {
+        let answer = Answer {
+            year,
+            day,
+            part,
+            input_file: file_name(input_file),
+            answer: answer.to_string(),
+        };
+
+        let key = |a: &Answer| (a.year, a.day, a.part, a.input_file.clone());
+
+        // Kept sorted so that the file diffs nicely
+        match self.answers.binary_search_by_key(&key(&answer), key) {
+            Ok(index) => self.answers[index] = answer,
+            Err(index) => self.answers.insert(index, answer),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Stats {
+    pub(super) iterations: usize,
+
+    pub(super) min: Duration,
+    pub(super) mean: Duration,
+    pub(super) median: Duration,
+    pub(super) p95: Duration,
+    pub(super) stddev: Duration,
+}
+
+impl Stats {
+    pub(super) fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort();
+
+        let n = samples.len();
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        // Nearest-rank percentile
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+
+        Some(Self {
+            iterations: n,
+            min: samples[0],
+            mean: Duration::from_secs_f64(mean),
+            median,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
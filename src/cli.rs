@@ -0,0 +1,395 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::cmd::ParsePathError;
+
+pub(super) const NAME: &str = env!("CARGO_PKG_NAME");
+pub(super) const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Error)]
+pub(super) enum UsageError {
+    #[error("no command given")]
+    MissingCommand,
+
+    #[error("unknown command `{0}`")]
+    UnknownCommand(String),
+
+    #[error("missing puzzle selection, give a path like `day1/part2` or `all`, or use --day")]
+    MissingSelection,
+
+    #[error("invalid puzzle path `{0}`")]
+    InvalidPath(String, #[source] ParsePathError),
+
+    #[error("option `{0}` requires a value")]
+    MissingValue(String),
+
+    #[error("invalid value `{value}` for option `{option}`, expected {expected}")]
+    InvalidValue {
+        option: String,
+        value: String,
+        expected: &'static str,
+    },
+
+    #[error("option `{0}` does not take a value")]
+    UnexpectedValue(String),
+
+    #[error("unknown option `{0}`")]
+    UnknownOption(String),
+
+    #[error("unexpected argument `{0}`")]
+    UnexpectedArgument(String),
+
+    #[error("`{0}` can not be used together with `{1}`")]
+    Conflict(String, String),
+
+    #[error("`{0}` requires a day, give a path like `day1` or use --day")]
+    MissingDay(String),
+
+    #[error("invalid day `{0}`, expected a positive number like `5` or `day5`")]
+    InvalidDay(String),
+
+    #[error("`{0}` requires a part, give a path like `day1/part2` or use --part")]
+    MissingPart(String),
+
+    #[error("can not tell the day and part of `{0}`, use --day and --part")]
+    UnresolvedFile(String),
+}
+
+/// A single command line argument, either an option with an optional inline `--option=value` or
+/// `-ovalue` value or a positional argument
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum Arg<'a> {
+    Option(&'a str, Option<&'a str>),
+    Positional(&'a str),
+}
+
+pub(super) struct Args<'a> {
+    args: std::slice::Iter<'a, String>,
+    only_positional: bool,
+}
+
+impl<'a> Args<'a> {
+    pub(super) fn new(args: &'a [String]) -> Self {
+        Self {
+            args: args.iter(),
+            only_positional: false,
+        }
+    }
+
+    /// Returns the value of an option, either given inline or as the next argument
+    pub(super) fn value(
+        &mut self,
+        option: &str,
+        inline: Option<&'a str>,
+    ) -> Result<&'a str, UsageError> {
+        inline
+            .or_else(|| self.args.next().map(String::as_str))
+            .ok_or(UsageError::MissingValue(option.to_string()))
+    }
+
+    pub(super) fn parse<T: FromStr>(
+        &mut self,
+        option: &str,
+        inline: Option<&'a str>,
+        expected: &'static str,
+    ) -> Result<T, UsageError> {
+        let value = self.value(option, inline)?;
+
+        value.parse().map_err(|_| UsageError::InvalidValue {
+            option: option.to_string(),
+            value: value.to_string(),
+            expected,
+        })
+    }
+
+    /// Ensures that a flag option was not given an inline value
+    pub(super) fn flag(&self, option: &str, inline: Option<&str>) -> Result<bool, UsageError> {
+        match inline {
+            Some(_) => Err(UsageError::UnexpectedValue(option.to_string())),
+            None => Ok(true),
+        }
+    }
+}
+
+impl<'a> Iterator for Args<'a> {
+    type Item = Arg<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let arg = self.args.next()?.as_str();
+
+        if self.only_positional || arg == "-" || !arg.starts_with('-') {
+            return Some(Arg::Positional(arg));
+        }
+
+        if arg == "--" {
+            self.only_positional = true;
+            return self.next();
+        }
+
+        if !arg.starts_with("--") {
+            // Short options take their value right after them, as in `-j1`
+            return Some(if arg.len() > 2 && arg.is_char_boundary(2) {
+                Arg::Option(&arg[..2], Some(&arg[2..]))
+            } else {
+                Arg::Option(arg, None)
+            });
+        }
+
+        Some(match arg.split_once('=') {
+            Some((option, value)) => Arg::Option(option, Some(value)),
+            None => Arg::Option(arg, None),
+        })
+    }
+}
+
+struct CommandHelp {
+    name: &'static str,
+    arguments: &'static str,
+    summary: &'static str,
+    options: &'static [&'static [&'static str]],
+}
+
+const SELECTION_OPTIONS: &[&str] = &[
+    "  -d, --day <N>            Day of the puzzle",
+    "  -p, --part <N>           Part of the puzzle",
+    "      --file <PATH>        Run against this file instead of searching the input directory,",
+    "                           `-` reads the standard input",
+    INPUT_DIR_OPTION,
+    "  -j, --jobs <N>           Number of puzzles to run in parallel [default: number of CPUs]",
+];
+
+const INPUT_DIR_OPTION: &str =
+    "      --input-dir <DIR>    Directory holding the input files [env: AOC_INPUT_DIR] [default: inputs]";
+
+const FORMAT_OPTIONS: &[&str] =
+    &["  -f, --format <FORMAT>    Output format: text, json, jsonl, csv or tap [default: text]"];
+
+const TEST_OPTIONS: &[&str] = &[
+    "      --junit <PATH>       Write a JUnit XML report to PATH",
+    "      --fail-fast          Stop at the first failing test",
+];
+
+const BENCH_OPTIONS: &[&str] = &[
+    "      --warmup <N>         Untimed runs before measuring [default: 5]",
+    "      --iterations <N>     Timed runs [default: 50]",
+];
+
+const CLIENT_OPTIONS: &[&str] = &[
+    "      --base-url <URL>     Website to talk to [env: AOC_BASE_URL]",
+    "      --session-file <PATH>",
+    "                           File holding the session token, used when AOC_SESSION is",
+    "                           not set [env: AOC_SESSION_FILE]",
+];
+
+const WATCH_OPTIONS: &[&str] =
+    &["  -w, --watch              Run again whenever an input file changes, showing what changed"];
+
+const TIMEOUT_OPTIONS: &[&str] = &[
+    "      --timeout <DURATION> Give up on a solver after DURATION, like `500ms`, `10s` or `2m`,",
+    "                           replacing the timeouts of aoc.toml [default: none]",
+];
+
+const HELP_OPTIONS: &[&str] = &["  -h, --help               Print help"];
+
+const COMMANDS: &[CommandHelp] = &[
+    CommandHelp {
+        name: "solve",
+        arguments: "[OPTIONS] [PATH] [-]",
+        summary: "Solve puzzles against their real inputs",
+        options: &[
+            SELECTION_OPTIONS,
+            FORMAT_OPTIONS,
+            TIMEOUT_OPTIONS,
+            WATCH_OPTIONS,
+            HELP_OPTIONS,
+        ],
+    },
+    CommandHelp {
+        name: "test",
+        arguments: "[OPTIONS] [PATH] [-]",
+        summary: "Check puzzles against their example inputs",
+        options: &[
+            SELECTION_OPTIONS,
+            FORMAT_OPTIONS,
+            TEST_OPTIONS,
+            TIMEOUT_OPTIONS,
+            WATCH_OPTIONS,
+            HELP_OPTIONS,
+        ],
+    },
+    CommandHelp {
+        name: "bench",
+        arguments: "[OPTIONS] [PATH] [-]",
+        summary: "Time repeated runs of puzzles against their real inputs",
+        options: &[SELECTION_OPTIONS, BENCH_OPTIONS, HELP_OPTIONS],
+    },
+    CommandHelp {
+        name: "submit",
+        arguments: "[OPTIONS] [PATH] [-]",
+        summary: "Solve a puzzle part against its real input and submit the answer",
+        options: &[
+            SELECTION_OPTIONS,
+            CLIENT_OPTIONS,
+            TIMEOUT_OPTIONS,
+            &[
+                "      --dry-run            Show what would be submitted without sending it",
+                "      --force              Submit even if previous feedback rules the answer out or",
+                "                           a wait is still pending",
+                "      --record             Save the answer to the answers file once accepted",
+            ],
+            HELP_OPTIONS,
+        ],
+    },
+    CommandHelp {
+        name: "verify",
+        arguments: "[OPTIONS] [PATH] [-]",
+        summary: "Check puzzles against the answers recorded for their real inputs",
+        options: &[
+            SELECTION_OPTIONS,
+            FORMAT_OPTIONS,
+            TIMEOUT_OPTIONS,
+            HELP_OPTIONS,
+        ],
+    },
+    CommandHelp {
+        name: "list",
+        arguments: "[OPTIONS]",
+        summary: "List registered puzzles and the input files available for them",
+        options: &[&[INPUT_DIR_OPTION], HELP_OPTIONS],
+    },
+    CommandHelp {
+        name: "new",
+        arguments: "[OPTIONS] <DAY>",
+        summary: "Generate the solver module and empty input files of a new day",
+        options: &[
+            &[
+                "      --name <NAME>        Title of the puzzle [default: Unknown]",
+                INPUT_DIR_OPTION,
+            ],
+            HELP_OPTIONS,
+        ],
+    },
+    CommandHelp {
+        name: "fetch",
+        arguments: "[OPTIONS] <DAY>",
+        summary: "Download the puzzle input of a day, unless it is already cached",
+        options: &[&[INPUT_DIR_OPTION], CLIENT_OPTIONS, HELP_OPTIONS],
+    },
+];
+
+fn find(command: Option<&str>) -> Option<&'static CommandHelp> {
+    command.and_then(|c| COMMANDS.iter().find(|h| h.name == c))
+}
+
+pub(super) fn is_command(command: &str) -> bool {
+    find(Some(command)).is_some()
+}
+
+/// One-line synopsis of a command, or of the program itself
+pub(super) fn synopsis(command: Option<&str>) -> String {
+    match find(command) {
+        Some(help) => format!("Usage: {} {} {}", NAME, help.name, help.arguments),
+        None => format!("Usage: {} <COMMAND> [OPTIONS] [PATH]", NAME),
+    }
+}
+
+/// Full help text of a command, or of the program itself
+pub(super) fn usage(command: Option<&str>) -> String {
+    let mut lines = Vec::new();
+
+    match find(command) {
+        Some(help) => {
+            lines.push(help.summary.to_string());
+            lines.push(String::new());
+            lines.push(synopsis(command));
+            lines.push(String::new());
+            lines.push("Options:".to_string());
+            lines.extend(
+                help.options
+                    .iter()
+                    .flat_map(|o| o.iter())
+                    .map(|o| o.to_string()),
+            );
+        }
+        None => {
+            lines.push("Advent of Code 2022 solutions".to_string());
+            lines.push(String::new());
+            lines.push(synopsis(None));
+            lines.push(String::new());
+            lines.push("Commands:".to_string());
+            for help in COMMANDS {
+                lines.push(format!("  {:<9}{}", help.name, help.summary));
+            }
+            lines.push(format!(
+                "  {:<9}Print this message or the help of the given command",
+                "help"
+            ));
+            lines.push(String::new());
+            lines.push("Options:".to_string());
+            lines.push("  -h, --help       Print help".to_string());
+            lines.push("  -V, --version    Print version".to_string());
+            lines.push(String::new());
+            lines.push(
+                "PATH selects puzzles and is a shorthand for --day and --part, for example".into(),
+            );
+            lines.push(
+                "`day1` or `day1/part2`. Indices can be lists and inclusive ranges like".into(),
+            );
+            lines.push(
+                "`day1,3` or `day1..3/part2`, `*` matches any index as in `day*/part1` and".into(),
+            );
+            lines.push(
+                "`all` selects every puzzle. A `-` after PATH reads the input of the selected"
+                    .into(),
+            );
+            lines.push("part from the standard input.".into());
+            lines.push(String::new());
+            lines.push(
+                "Example files are named like `day1.part2.test.txt` or `day1.part2.test3.txt`."
+                    .into(),
+            );
+            lines.push(
+                "Their answer is read from a sidecar file like `day1.part2.test3.expected`, from a"
+                    .into(),
+            );
+            lines.push("first line like `# expected: 42` or else given by the solver.".into());
+            lines.push(
+                "They are tested on top of the examples embedded in the solvers, which paths like"
+                    .into(),
+            );
+            lines.push("`day1/part2/example1` select on their own.".into());
+            lines.push(String::new());
+            lines.push(
+                "Defaults for input-dir, format, jobs, timeout, base-url and session-file can be"
+                    .into(),
+            );
+            lines.push(
+                "set in an aoc.toml file, looked up from the current directory upwards. Its".into(),
+            );
+            lines.push("[timeouts] table overrides the timeout of single days, as in".into());
+            lines.push("`day5 = \"1m\"`.".into());
+            lines.push(String::new());
+            lines.push(format!(
+                "Run `{} help <COMMAND>` for the options of a command.",
+                NAME
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Short hint printed after a usage error
+pub(super) fn hint(command: Option<&str>) -> String {
+    let help = match find(command) {
+        Some(help) => format!("{} help {}", NAME, help.name),
+        None => format!("{} --help", NAME),
+    };
+
+    format!(
+        "{}\n\nFor more information, try `{}`.",
+        synopsis(command),
+        help
+    )
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::cli;
+
+pub(super) const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub(super) const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub(super) const SESSION_ENV: &str = "AOC_SESSION";
+pub(super) const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/oktal/aoc2k22)"
+);
+
+#[derive(Debug, Error)]
+pub(super) enum Error {
+    #[error("no session token, set {SESSION_ENV} or write it to {0:?}")]
+    MissingSession(PathBuf),
+
+    #[error("failed to read session token from {0:?}")]
+    ReadSession(PathBuf, #[source] io::Error),
+
+    #[error("{url} answered with status {status}: {body}")]
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+
+    #[error("request to {0} failed")]
+    Transport(String, #[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// Resolves the base URL of the website, `base_url` taking precedence over the environment and
+/// then over the `configured` one
+pub(super) fn base_url(base_url: Option<&str>, configured: Option<&str>) -> String {
+    base_url
+        .map(String::from)
+        .or_else(|| std::env::var(BASE_URL_ENV).ok())
+        .or_else(|| configured.map(String::from))
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
+
+fn default_session_file() -> PathBuf {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    home.join(".config").join(cli::NAME).join("session")
+}
+
+/// Reads the session token from the environment, or from `session_file`, falling back to the
+/// file named by the environment, then to the `configured` one and then to the default location
+pub(super) fn session(
+    session_file: Option<&Path>,
+    configured: Option<&Path>,
+) -> Result<String, Error> {
+    if let Ok(session) = std::env::var(SESSION_ENV) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    let path = session_file
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::var_os(SESSION_FILE_ENV).map(PathBuf::from))
+        .or_else(|| configured.map(Path::to_path_buf))
+        .unwrap_or_else(default_session_file);
+
+    let session = match fs::read_to_string(&path) {
+        Ok(session) => session,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(Error::MissingSession(path)),
+        Err(e) => return Err(Error::ReadSession(path, e)),
+    };
+
+    match session.trim() {
+        "" => Err(Error::MissingSession(path)),
+        session => Ok(session.to_string()),
+    }
+}
+
+pub(super) struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub(super) fn new(base_url: &str, session: String) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn read(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, Error> {
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| Error::Transport(url.to_string(), e.into())),
+            Err(ureq::Error::Status(status, response)) => Err(Error::Status {
+                url: url.to_string(),
+                status,
+                body: response
+                    .into_string()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            }),
+            Err(e) => Err(Error::Transport(url.to_string(), e.into())),
+        }
+    }
+
+    /// Downloads the puzzle input of a day
+    pub(super) fn input(&self, year: u16, day: usize) -> Result<String, Error> {
+        let url = self.url(&format!("/{}/day/{}/input", year, day));
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        Self::read(&url, response)
+    }
+
+    /// Submits the answer of a part and returns the raw page the website answered with
+    pub(super) fn answer(
+        &self,
+        year: u16,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> Result<String, Error> {
+        let url = self.url(&answer_path(year, day));
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        Self::read(&url, response)
+    }
+}
+
+pub(super) fn answer_path(year: u16, day: usize) -> String {
+    format!("/{}/day/{}/answer", year, day)
+}
@@ -1,26 +1,113 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::ops::{ControlFlow, RangeInclusive};
 use std::str::FromStr;
 use std::string::String;
 use std::vec::Vec;
 
 use std::fs;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 
+use std::thread;
 use std::time;
 
+use crate::answers::{self, Answers};
+use crate::bench::Stats;
+use crate::cli::{self, Arg, UsageError};
+use crate::client::{self, Client};
+use crate::config::{self, Config, Timeouts};
 use crate::day;
+use crate::junit;
+use crate::pool;
+use crate::report::{self, Format, Summary};
+use crate::scaffold;
+use crate::submission::{self, Database, Feedback, Outcome, Submission};
+use crate::watch::{self, Watcher};
 
-#[derive(Debug)]
+use thiserror::Error;
+
+#[derive(Debug, Error)]
 pub(super) enum ParsePathError {
+    #[error("empty puzzle path")]
     Empty,
 
+    #[error("{0:?} is not a valid puzzle path")]
     InvalidPath(PathBuf),
-    InvalidIndex(String, std::num::ParseIntError),
+
+    #[error("invalid index in `{0}`")]
+    InvalidIndex(String, #[source] std::num::ParseIntError),
+
+    #[error("range in `{0}` ends before it starts")]
+    InvalidRange(String),
+}
+
+/// Indices selected by a path fragment, `*` selecting any of them
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Indices {
+    Any,
+    Ranges(Vec<RangeInclusive<usize>>),
+}
+
+impl Indices {
+    /// Parses `*` or a comma separated list of indices and inclusive `first..last` ranges
+    fn parse(s: &str, fragment: &str) -> std::result::Result<Self, ParsePathError> {
+        if s == "*" {
+            return Ok(Indices::Any);
+        }
+
+        let index = |i: &str| {
+            i.parse::<usize>()
+                .map_err(|e| ParsePathError::InvalidIndex(fragment.into(), e))
+        };
+
+        let mut ranges = Vec::new();
+        for item in s.split(',') {
+            match item.split_once("..") {
+                Some((first, last)) => {
+                    let (first, last) = (index(first)?, index(last)?);
+                    if first > last {
+                        return Err(ParsePathError::InvalidRange(fragment.into()));
+                    }
+
+                    ranges.push(first..=last);
+                }
+                None => {
+                    let index = index(item)?;
+                    ranges.push(index..=index);
+                }
+            }
+        }
+
+        Ok(Indices::Ranges(ranges))
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        match self {
+            Indices::Any => true,
+            Indices::Ranges(ranges) => ranges.iter().any(|r| r.contains(&index)),
+        }
+    }
+
+    /// Returns the index if exactly one is selected
+    fn single(&self) -> Option<usize> {
+        match self {
+            Indices::Any => None,
+            Indices::Ranges(ranges) => {
+                let index = *ranges.first()?.start();
+                ranges
+                    .iter()
+                    .all(|r| *r.start() == index && *r.end() == index)
+                    .then_some(index)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 struct ArgPathFragment {
     prefix: String,
-    index: Option<usize>,
+    indices: Option<Indices>,
 }
 
 impl ArgPathFragment {
@@ -29,21 +116,17 @@ impl ArgPathFragment {
             return Err(ParsePathError::Empty);
         }
 
-        let (prefix, index) = match s.find(|c: char| c.is_ascii_digit()) {
+        let (prefix, indices) = match s.find(|c: char| c.is_ascii_digit() || c == '*') {
             Some(idx) => {
-                let (prefix, index) = s.split_at(idx);
-                let index = index
-                    .parse::<usize>()
-                    .map_err(|e| ParsePathError::InvalidIndex(s.into(), e))?;
-
-                (prefix, Some(index))
+                let (prefix, indices) = s.split_at(idx);
+                (prefix, Some(Indices::parse(indices, s)?))
             }
             None => (s, None),
         };
 
         Ok(ArgPathFragment {
             prefix: prefix.into(),
-            index,
+            indices,
         })
     }
 }
@@ -60,9 +143,12 @@ impl ArgPath {
             return Err(ParsePathError::Empty);
         }
 
+        // `all` selects every puzzle and only narrows down with the fragments that follow it
         let fragments = s
             .split('/')
-            .map(ArgPathFragment::parse)
+            .enumerate()
+            .filter(|&(i, f)| !(i == 0 && f == "all"))
+            .map(|(_, f)| ArgPathFragment::parse(f))
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(ArgPath {
@@ -94,24 +180,49 @@ impl ArgPath {
         })
     }
 
+    /// Path selecting every puzzle
+    fn all() -> Self {
+        ArgPath {
+            value: "all".into(),
+            fragments: Vec::new(),
+        }
+    }
+
     fn fragment(&self, prefix: &str) -> Option<&ArgPathFragment> {
         self.fragments.iter().find(|&f| f.prefix == prefix)
     }
 
+    /// Returns the index of a fragment if it selects exactly one
     fn fragment_index(&self, prefix: &str) -> Option<usize> {
-        self.fragment(prefix).and_then(|f| f.index)
+        self.fragment(prefix)
+            .and_then(|f| f.indices.as_ref())
+            .and_then(Indices::single)
     }
 
-    fn disjoint(&self, other: &ArgPath) -> Option<&ArgPathFragment> {
-        for i in 0..self.fragments.len() {
-            if i >= other.fragments.len() {
-                return Some(&self.fragments[i]);
-            } else if self.fragments[i] != other.fragments[i] {
-                return Some(&self.fragments[i]);
-            }
-        }
+    /// Whether `index` is selected by the fragment with the given prefix, anything being selected
+    /// when there is no such fragment or it has no index
+    fn selects(&self, prefix: &str, index: usize) -> bool {
+        self.fragment(prefix)
+            .and_then(|f| f.indices.as_ref())
+            .is_none_or(|indices| indices.contains(index))
+    }
 
-        None
+    /// Whether the file named by `file` is selected by this path. A file without a part is
+    /// selected whatever part this path asks for, as the input of a day is shared by its parts
+    fn matches(&self, file: &ArgPath) -> bool {
+        self.fragments
+            .iter()
+            .all(|fragment| match file.fragment(&fragment.prefix) {
+                Some(f) => match (
+                    &fragment.indices,
+                    f.indices.as_ref().and_then(Indices::single),
+                ) {
+                    (None, _) => true,
+                    (Some(indices), Some(index)) => indices.contains(index),
+                    (Some(_), None) => false,
+                },
+                None => fragment.prefix == "part",
+            })
     }
 }
 
@@ -126,27 +237,129 @@ impl FromStr for ArgPath {
 #[derive(Debug)]
 pub(super) struct CommonArgs {
     path: ArgPath,
+    file: Option<PathBuf>,
+    input_dir: Option<PathBuf>,
+    jobs: Option<usize>,
+    format: Option<Format>,
+    timeout: Option<time::Duration>,
+    watch: bool,
 }
 
 #[derive(Debug)]
-pub(super) enum Error {
-    MissingCommand,
-    MissingPath(String),
+pub(super) struct BenchArgs {
+    warmup: usize,
+    iterations: usize,
+}
+
+impl Default for BenchArgs {
+    fn default() -> Self {
+        Self {
+            warmup: 5,
+            iterations: 50,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct TestArgs {
+    junit: Option<PathBuf>,
+    fail_fast: bool,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ListArgs {
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub(super) struct NewArgs {
+    day: usize,
+    name: String,
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct SubmitArgs {
+    base_url: Option<String>,
+    session_file: Option<PathBuf>,
+    dry_run: bool,
+    force: bool,
+    record: bool,
+}
 
-    InvalidCommand(String),
-    InvalidPath(ParsePathError),
+#[derive(Debug)]
+pub(super) struct FetchArgs {
+    day: usize,
+    input_dir: Option<PathBuf>,
+    base_url: Option<String>,
+    session_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Error)]
+pub(super) enum Error {
+    #[error("{1}")]
+    Usage(Option<String>, UsageError),
 
+    #[error("can not tell the day and part of {0:?}")]
     ResolvePath(PathBuf),
 
-    ReadInputDirectory(PathBuf, std::io::Error),
+    #[error("failed to get the current directory")]
+    CurrentDirectory(#[source] std::io::Error),
+    #[error("failed to read the input directory {0:?}")]
+    ReadInputDirectory(PathBuf, #[source] std::io::Error),
+    #[error("failed to read the standard input")]
+    ReadStdin(#[source] std::io::Error),
+    #[error("failed to write the report {0:?}")]
+    WriteReport(PathBuf, #[source] std::io::Error),
+    #[error("failed to write the input file {0:?}")]
+    WriteInput(PathBuf, #[source] std::io::Error),
+
+    #[error("failed to solve {0:?}")]
+    Solver(PathBuf, #[source] day::SolverError),
+
+    #[error(transparent)]
+    Config(config::Error),
+    #[error(transparent)]
+    Scaffold(scaffold::Error),
+    #[error(transparent)]
+    Client(client::Error),
+    #[error(transparent)]
+    Submission(submission::Error),
+    #[error(transparent)]
+    Answers(answers::Error),
+    #[error("more than one input file matches, select one of {0:?}")]
+    AmbiguousInput(Vec<PathBuf>),
+    #[error("refusing to submit {0} as {}, use --force to submit anyway", .1.join(" and "))]
+    ImpossibleAnswer(String, Vec<String>),
+    #[error("refusing to submit for another {0:?} as the website asked to wait, use --force to submit anyway")]
+    RateLimited(time::Duration),
+}
+
+/// Describes an error followed by its causes, like `failed to read "x": No such file`
+fn describe(e: &dyn std::error::Error) -> String {
+    let mut description = e.to_string();
+
+    let mut source = e.source();
+    while let Some(cause) = source {
+        description.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
 
-    SolverError(PathBuf, day::SolverError),
+    description
 }
 
 #[derive(Debug)]
 pub(super) enum Command {
     Solve(CommonArgs),
-    Test(CommonArgs),
+    Test(CommonArgs, TestArgs),
+    Bench(CommonArgs, BenchArgs),
+    Submit(CommonArgs, SubmitArgs),
+    Verify(CommonArgs),
+    List(ListArgs),
+    New(NewArgs),
+    Fetch(FetchArgs),
+    Help(Option<String>),
+    Version,
 }
 
 pub(super) type Result<T> = std::result::Result<T, Error>;
@@ -162,14 +375,216 @@ fn read_input_files<P: AsRef<Path>>(path: P) -> Result<Vec<PathBuf>> {
             entry.map_err(|e| Error::ReadInputDirectory(PathBuf::from(path.as_ref()), e))?;
         let path = entry.path();
 
-        if path.is_file() {
-            input_files.push(path.into())
+        // Other files such as the submissions database live alongside the inputs
+        if path.is_file() && path.extension().is_some_and(|e| e == "txt") {
+            input_files.push(path)
         }
     }
 
     Ok(input_files)
 }
 
+fn parse_day(s: &str) -> std::result::Result<usize, UsageError> {
+    s.strip_prefix("day")
+        .unwrap_or(s)
+        .parse::<NonZeroUsize>()
+        .map(NonZeroUsize::get)
+        .map_err(|_| UsageError::InvalidDay(s.to_string()))
+}
+
+/// File name standing for the standard input
+const STDIN: &str = "-";
+
+struct Job {
+    day: usize,
+    part: usize,
+    input_file: PathBuf,
+
+    /// The example to test when the job is about one embedded in a day module, `input_file`
+    /// then only naming it
+    example: Option<day::Example>,
+}
+
+impl Job {
+    /// The input of the job, `stdin` holding what was read from the standard input if anything
+    fn input<'a>(&'a self, stdin: Option<&'a [u8]>) -> day::Input<'a> {
+        match (self.example, stdin) {
+            (Some((example, ..)), _) => day::Input::Data(&self.input_file, example.as_bytes()),
+            (None, Some(data)) if self.input_file == Path::new(STDIN) => {
+                day::Input::Data(&self.input_file, data)
+            }
+            _ => day::Input::File(&self.input_file),
+        }
+    }
+}
+
+/// Result of running a job, along with the answer it was checked against
+struct Run {
+    expected: Option<String>,
+    result: day::SolverResult,
+    metrics: day::Metrics,
+    elapsed: time::Duration,
+}
+
+/// Runs jobs of one kind and turns their results into records, along with what they are
+/// checked against
+struct Runner {
+    kind: report::Kind,
+    timeouts: Timeouts,
+    answers: Option<Answers>,
+    database: Option<Database>,
+}
+
+impl Runner {
+    fn load(kind: report::Kind, input_dir: &Path, timeouts: Timeouts) -> Result<Self> {
+        let answers = match kind {
+            report::Kind::Verify => {
+                Some(Answers::load(&Answers::path(input_dir)).map_err(Error::Answers)?)
+            }
+            report::Kind::Solve | report::Kind::Test => None,
+        };
+
+        let database = match kind {
+            report::Kind::Solve => {
+                Some(Database::load(&Database::path(input_dir)).map_err(Error::Submission)?)
+            }
+            report::Kind::Test | report::Kind::Verify => None,
+        };
+
+        Ok(Self {
+            kind,
+            timeouts,
+            answers,
+            database,
+        })
+    }
+
+    /// The answer the job is checked against. Test fixtures may hold their own, in a sidecar file
+    /// or a header line, before falling back to the one of their solver
+    fn expected(
+        &self,
+        job: &Job,
+        stdin: Option<&[u8]>,
+    ) -> std::result::Result<Option<String>, day::SolverError> {
+        match self.kind {
+            report::Kind::Solve => Ok(None),
+            report::Kind::Test => {
+                let fixture = match job.example {
+                    Some((_, _, expected)) => Some(expected.to_string()),
+                    None => day::fixture_expected(job.input(stdin))?,
+                };
+
+                Ok(fixture.or_else(|| day::expected(job.day, job.part).map(String::from)))
+            }
+            report::Kind::Verify => Ok(self
+                .answers
+                .as_ref()
+                .and_then(|a| a.get(day::YEAR, job.day, job.part, &job.input_file))
+                .map(String::from)),
+        }
+    }
+
+    fn run(&self, job: &Job, stdin: Option<&[u8]>) -> Run {
+        let timeout = self.timeouts.get(job.day);
+
+        let start = time::Instant::now();
+        let expected = match self.expected(job, stdin) {
+            Ok(expected) => expected,
+            Err(e) => {
+                return Run {
+                    expected: None,
+                    result: Err(e),
+                    metrics: day::Metrics::default(),
+                    elapsed: start.elapsed(),
+                }
+            }
+        };
+
+        let (result, metrics) = match self.kind {
+            report::Kind::Solve => day::solve(job.input(stdin), job.day, job.part, timeout),
+            report::Kind::Test => day::test(
+                job.input(stdin),
+                job.day,
+                job.part,
+                expected.as_deref(),
+                timeout,
+            ),
+            report::Kind::Verify => {
+                let (result, metrics) = day::solve(job.input(stdin), job.day, job.part, timeout);
+                let result = result.and_then(|got| match &expected {
+                    Some(expected) if *expected != got => Err(day::SolverError::Test {
+                        got,
+                        expected: expected.clone(),
+                    }),
+                    _ => Ok(got),
+                });
+
+                (result, metrics)
+            }
+        };
+
+        Run {
+            expected,
+            result,
+            metrics,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    fn record(&self, job: &Job, run: &Run) -> report::Record {
+        let Run {
+            expected,
+            result,
+            metrics,
+            elapsed,
+        } = run;
+
+        let (status, answer, error) = match result {
+            Ok(answer) => (report::Status::Ok, Some(answer.clone()), None),
+            Err(e @ day::SolverError::Test { got, .. }) => (
+                report::Status::Failed,
+                Some(got.clone()),
+                Some(describe(&e)),
+            ),
+            Err(e @ day::SolverError::Timeout(_)) => {
+                (report::Status::Timeout, None, Some(describe(&e)))
+            }
+            Err(e) => (report::Status::Error, None, Some(describe(&e))),
+        };
+
+        // Answers are checked against what the website said about previous submissions
+        let warnings = match (&self.database, &answer) {
+            (Some(database), Some(answer)) => database.check(day::YEAR, job.day, job.part, answer),
+            _ => Vec::new(),
+        };
+
+        report::Record {
+            kind: self.kind,
+            day: job.day,
+            part: job.part,
+            name: day::name(job.day).unwrap_or("Unknown"),
+            input_file: job.input_file.to_path_buf(),
+            status,
+            answer,
+            expected: expected.clone(),
+            error,
+            snippet: result.as_ref().err().and_then(day::SolverError::snippet),
+            warnings,
+            elapsed: *elapsed,
+            read: metrics.read,
+            parse: metrics.parse,
+            solve: metrics.solve,
+            memory: metrics.memory,
+        }
+    }
+}
+
+fn warn_skipped(skipped: &[PathBuf]) {
+    for file in skipped {
+        eprintln!("WARN skipping file with unknown puzzle or type {:?}", file);
+    }
+}
+
 #[derive(Eq, PartialEq)]
 enum FileType {
     Input,
@@ -197,136 +612,1018 @@ impl Command {
     }
 
     fn parse(args: Vec<String>) -> Result<Self> {
-        let command = args.get(0).ok_or(Error::MissingCommand)?;
-        let command = command.to_lowercase();
+        let mut iter = cli::Args::new(&args);
+
+        let command = match iter.next() {
+            None => return Err(Error::Usage(None, UsageError::MissingCommand)),
+            Some(Arg::Option("-h" | "--help", None)) => return Ok(Command::Help(None)),
+            Some(Arg::Option("-V" | "--version", None)) => return Ok(Command::Version),
+            Some(Arg::Option(option, _)) => {
+                return Err(Error::Usage(
+                    None,
+                    UsageError::UnknownOption(option.to_string()),
+                ))
+            }
+            Some(Arg::Positional(command)) => command.to_lowercase(),
+        };
+
+        if command == "help" {
+            return match iter.next() {
+                None => Ok(Command::Help(None)),
+                Some(Arg::Positional(topic)) if cli::is_command(topic) => {
+                    Ok(Command::Help(Some(topic.to_string())))
+                }
+                Some(Arg::Positional(topic)) => Err(Error::Usage(
+                    None,
+                    UsageError::UnknownCommand(topic.to_string()),
+                )),
+                Some(Arg::Option(option, _)) => Err(Error::Usage(
+                    None,
+                    UsageError::UnknownOption(option.to_string()),
+                )),
+            };
+        }
 
-        let is_valid = matches!(command.as_str(), "test" | "solve");
-        if !is_valid {
-            return Err(Error::InvalidCommand(command));
+        if !cli::is_command(&command) {
+            return Err(Error::Usage(None, UsageError::UnknownCommand(command)));
         }
 
-        let path = args
-            .get(1)
-            .ok_or(Error::MissingPath(command.clone()))
-            .and_then(|p| ArgPath::from_str(p.as_str()).map_err(Error::InvalidPath))?;
+        Self::parse_command(&command, iter).map_err(|e| Error::Usage(Some(command), e))
+    }
+
+    fn parse_list(mut args: cli::Args) -> std::result::Result<Self, UsageError> {
+        let mut list = ListArgs::default();
+
+        while let Some(arg) = args.next() {
+            match arg {
+                Arg::Option("-h" | "--help", None) => {
+                    return Ok(Command::Help(Some("list".to_string())))
+                }
+                Arg::Option(o @ "--input-dir", v) => {
+                    list.input_dir = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o, _) => return Err(UsageError::UnknownOption(o.to_string())),
+                Arg::Positional(p) => return Err(UsageError::UnexpectedArgument(p.to_string())),
+            }
+        }
 
-        let args = CommonArgs { path };
-        Ok(match command.as_str() {
-            "test" => Command::Test(args),
+        Ok(Command::List(list))
+    }
+
+    fn parse_new(mut args: cli::Args) -> std::result::Result<Self, UsageError> {
+        let mut day = None;
+        let mut name = None;
+        let mut input_dir = None;
+
+        while let Some(arg) = args.next() {
+            match arg {
+                Arg::Option("-h" | "--help", None) => {
+                    return Ok(Command::Help(Some("new".to_string())))
+                }
+                Arg::Option(o @ "--name", v) => name = Some(args.value(o, v)?.to_string()),
+                Arg::Option(o @ "--input-dir", v) => {
+                    input_dir = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o, _) => return Err(UsageError::UnknownOption(o.to_string())),
+                Arg::Positional(p) if day.is_none() => day = Some(parse_day(p)?),
+                Arg::Positional(p) => return Err(UsageError::UnexpectedArgument(p.to_string())),
+            }
+        }
+
+        Ok(Command::New(NewArgs {
+            day: day.ok_or(UsageError::MissingDay("new".into()))?,
+            name: name.unwrap_or_else(|| "Unknown".to_string()),
+            input_dir,
+        }))
+    }
+
+    fn parse_fetch(mut args: cli::Args) -> std::result::Result<Self, UsageError> {
+        let mut day = None;
+        let mut input_dir = None;
+        let mut base_url = None;
+        let mut session_file = None;
+
+        while let Some(arg) = args.next() {
+            match arg {
+                Arg::Option("-h" | "--help", None) => {
+                    return Ok(Command::Help(Some("fetch".to_string())))
+                }
+                Arg::Option(o @ "--input-dir", v) => {
+                    input_dir = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o @ "--base-url", v) => base_url = Some(args.value(o, v)?.to_string()),
+                Arg::Option(o @ "--session-file", v) => {
+                    session_file = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o, _) => return Err(UsageError::UnknownOption(o.to_string())),
+                Arg::Positional(p) if day.is_none() => day = Some(parse_day(p)?),
+                Arg::Positional(p) => return Err(UsageError::UnexpectedArgument(p.to_string())),
+            }
+        }
+
+        Ok(Command::Fetch(FetchArgs {
+            day: day.ok_or(UsageError::MissingDay("fetch".into()))?,
+            input_dir,
+            base_url,
+            session_file,
+        }))
+    }
+
+    fn parse_command(command: &str, mut args: cli::Args) -> std::result::Result<Self, UsageError> {
+        match command {
+            "list" => return Self::parse_list(args),
+            "new" => return Self::parse_new(args),
+            "fetch" => return Self::parse_fetch(args),
+            _ => {}
+        }
+
+        let is_bench = command == "bench";
+        let is_test = command == "test";
+        let is_submit = command == "submit";
+        // Benchmarks run each solver many times in a row, a timeout would not mean much there
+        let has_timeout = !is_bench;
+        let has_format = matches!(command, "solve" | "test" | "verify");
+        let can_watch = matches!(command, "solve" | "test");
+
+        let mut path = None;
+        let mut day = None;
+        let mut part = None;
+        let mut file = None;
+        let mut input_dir = None;
+        let mut stdin = false;
+        let mut watch = false;
+
+        let mut jobs = None;
+        let mut format = None;
+        let mut timeout = None;
+        let mut bench = BenchArgs::default();
+        let mut test = TestArgs::default();
+        let mut submit = SubmitArgs::default();
+
+        const POSITIVE: &str = "a positive number";
+
+        while let Some(arg) = args.next() {
+            match arg {
+                Arg::Positional(STDIN) => stdin = true,
+                Arg::Positional(p) if path.is_none() => {
+                    path = Some(
+                        ArgPath::parse(p).map_err(|e| UsageError::InvalidPath(p.to_string(), e))?,
+                    );
+                }
+                Arg::Positional(p) => return Err(UsageError::UnexpectedArgument(p.to_string())),
+                Arg::Option("-h" | "--help", None) => {
+                    return Ok(Command::Help(Some(command.to_string())))
+                }
+                Arg::Option(o @ ("-d" | "--day"), v) => {
+                    day = Some(args.parse::<NonZeroUsize>(o, v, POSITIVE)?.get())
+                }
+                Arg::Option(o @ ("-p" | "--part"), v) => {
+                    part = Some(args.parse::<NonZeroUsize>(o, v, POSITIVE)?.get())
+                }
+                Arg::Option(o @ "--file", v) => file = Some(PathBuf::from(args.value(o, v)?)),
+                Arg::Option(o @ "--input-dir", v) => {
+                    input_dir = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o @ ("-j" | "--jobs"), v) => {
+                    jobs = Some(args.parse::<NonZeroUsize>(o, v, POSITIVE)?.get())
+                }
+                Arg::Option(o @ ("-f" | "--format"), v) if has_format => {
+                    format = Some(args.parse(o, v, "one of text, json, jsonl, csv or tap")?)
+                }
+                Arg::Option(o @ "--timeout", v) if has_timeout => {
+                    timeout = Some(
+                        args.parse::<day::Timeout>(o, v, "a duration like `500ms`, `10s` or `2m`")?
+                            .0,
+                    )
+                }
+                Arg::Option(o @ ("-w" | "--watch"), v) if can_watch => watch = args.flag(o, v)?,
+                Arg::Option(o @ "--fail-fast", v) if is_test => test.fail_fast = args.flag(o, v)?,
+                Arg::Option(o @ "--junit", v) if is_test => {
+                    test.junit = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o @ "--base-url", v) if is_submit => {
+                    submit.base_url = Some(args.value(o, v)?.to_string())
+                }
+                Arg::Option(o @ "--session-file", v) if is_submit => {
+                    submit.session_file = Some(PathBuf::from(args.value(o, v)?))
+                }
+                Arg::Option(o @ "--dry-run", v) if is_submit => submit.dry_run = args.flag(o, v)?,
+                Arg::Option(o @ "--force", v) if is_submit => submit.force = args.flag(o, v)?,
+                Arg::Option(o @ "--record", v) if is_submit => submit.record = args.flag(o, v)?,
+                Arg::Option(o @ "--warmup", v) if is_bench => {
+                    bench.warmup = args.parse(o, v, "a number")?
+                }
+                Arg::Option(o @ "--iterations", v) if is_bench => {
+                    bench.iterations = args.parse::<NonZeroUsize>(o, v, POSITIVE)?.get()
+                }
+                Arg::Option(o, _) => return Err(UsageError::UnknownOption(o.to_string())),
+            }
+        }
+
+        if stdin {
+            if file.is_some() {
+                return Err(UsageError::Conflict(STDIN.into(), "--file".into()));
+            }
+
+            file = Some(PathBuf::from(STDIN));
+        }
+
+        // Watching prints its own text output and needs to read the inputs again and again
+        if watch {
+            let conflict = match (stdin, format, &test.junit) {
+                (true, _, _) => Some(STDIN),
+                (_, Some(_), _) => Some("--format"),
+                (_, _, Some(_)) => Some("--junit"),
+                _ => None,
+            };
+
+            if let Some(conflict) = conflict {
+                return Err(UsageError::Conflict("--watch".into(), conflict.into()));
+            }
+        }
+
+        let path = match (path, day, part, &file) {
+            // Verifying is about catching regressions anywhere, so it covers every puzzle by default
+            (None, None, None, None) if command == "verify" => ArgPath::all(),
+            (path, day, part, file) => Self::selection(path, day, part, file.as_deref())?,
+        };
+
+        if is_submit && path.fragment("part").is_none() {
+            return Err(UsageError::MissingPart(command.to_string()));
+        }
+
+        let args = CommonArgs {
+            path,
+            file,
+            input_dir,
+            jobs,
+            format,
+            timeout,
+            watch,
+        };
+
+        Ok(match command {
+            "test" => Command::Test(args, test),
             "solve" => Command::Solve(args),
+            "bench" => Command::Bench(args, bench),
+            "submit" => Command::Submit(args, submit),
+            "verify" => Command::Verify(args),
             _ => unreachable!(),
         })
     }
 
+    /// Merges the positional path shorthand with the --day and --part options
+    fn selection(
+        path: Option<ArgPath>,
+        day: Option<usize>,
+        part: Option<usize>,
+        file: Option<&Path>,
+    ) -> std::result::Result<ArgPath, UsageError> {
+        let from_file = file.and_then(|f| ArgPath::parse_path(f).ok());
+
+        let mut value = match (path, day) {
+            (Some(path), Some(_)) => return Err(UsageError::Conflict(path.value, "--day".into())),
+            (Some(path), None) => path.value,
+            (None, Some(day)) => format!("day{}", day),
+            (None, None) => match from_file.as_ref().and_then(|f| f.fragment_index("day")) {
+                Some(day) => format!("day{}", day),
+                None if part.is_some() => return Err(UsageError::MissingDay("--part".into())),
+                None => return Err(UsageError::MissingSelection),
+            },
+        };
+
+        if let Some(part) = part {
+            if ArgPath::parse(&value).is_ok_and(|p| p.fragment("part").is_some()) {
+                return Err(UsageError::Conflict(value, "--part".into()));
+            }
+
+            value = format!("{}/part{}", value, part);
+        }
+
+        let mut path = ArgPath::parse(&value).map_err(|e| UsageError::InvalidPath(value, e))?;
+
+        // Running a single file needs to know exactly which solver to run it with
+        if let Some(file) = file {
+            let day = path.fragment_index("day");
+            let part = path
+                .fragment_index("part")
+                .or_else(|| from_file.as_ref().and_then(|f| f.fragment_index("part")));
+
+            let (day, part) = day
+                .zip(part)
+                .ok_or(UsageError::UnresolvedFile(file.display().to_string()))?;
+
+            let value = format!("day{}/part{}", day, part);
+            path = ArgPath::parse(&value).map_err(|e| UsageError::InvalidPath(value, e))?;
+        }
+
+        Ok(path)
+    }
+
     fn args(&self) -> &CommonArgs {
         match self {
-            Self::Solve(args) | Self::Test(args) => args,
+            Self::Solve(args)
+            | Self::Test(args, _)
+            | Self::Bench(args, _)
+            | Self::Submit(args, _)
+            | Self::Verify(args) => args,
+            Self::List(_) | Self::New(_) | Self::Fetch(_) | Self::Help(_) | Self::Version => {
+                unreachable!("{:?} does not run puzzles", self)
+            }
         }
     }
 
+    /// Resolves the input files of the selected puzzles, adding the files whose puzzle or type is
+    /// unknown to `skipped`
     fn resolve_input_files<P: AsRef<Path>>(
         &self,
         prefix_path: P,
+        skipped: &mut Vec<PathBuf>,
     ) -> Result<Vec<(ArgPath, PathBuf)>> {
         let args = self.args();
-
-        let arg_fragment = args.path.fragment_index("part");
-        let is_test = matches!(self, Self::Test(_));
+        let is_test = matches!(self, Self::Test(..));
 
         let mut input_files = Vec::new();
 
         let files = read_input_files(prefix_path)?;
         for file in &files {
-            let file_path = ArgPath::parse_path(&file).map_err(Error::InvalidPath)?;
-            if let Some(file_type) = get_file_type(&file_path) {
-                if let Some(fragment) = file_path.disjoint(&args.path) {
-                    if fragment.prefix == "part" {
-                        if file_type == FileType::Test && !is_test
-                            || file_type == FileType::Input && is_test
-                        {
-                            continue;
-                        }
-
-                        match (arg_fragment, fragment.index) {
-                            (Some(arg_fragment), Some(fragment)) if arg_fragment == fragment => {
-                                input_files.push((file_path, file.to_path_buf()));
-                            }
-                            (None, _) => input_files.push((file_path, file.to_path_buf())),
-                            _ => {}
-                        };
-                    } else if fragment.prefix == "input" && !is_test {
-                        input_files.push((file_path, file.to_path_buf()));
-                    } else if fragment.prefix == "test" && is_test {
+            // Files whose name doesn't tell their puzzle are skipped, as `list` does
+            let typed = ArgPath::parse_path(file)
+                .ok()
+                .and_then(|p| get_file_type(&p).map(|t| (p, t)));
+            match typed {
+                Some((file_path, file_type)) => {
+                    if (file_type == FileType::Test) == is_test && args.path.matches(&file_path) {
                         input_files.push((file_path, file.to_path_buf()));
                     }
-                } else {
-                    input_files.push((file_path, file.to_path_buf()));
                 }
-            } else {
-                println!("WARN skipping file with unknown type {:?}", file);
+                None => skipped.push(file.to_path_buf()),
             }
         }
 
         Ok(input_files)
     }
 
-    pub(super) fn run(&self, prefix_path: impl AsRef<Path>) -> Result<()> {
-        let input_files = self.resolve_input_files(prefix_path)?;
-
-        if input_files.is_empty() {
-            let args = self.args();
-            println!("Could not find any input files for {}", args.path.value);
-        } else {
-            for (path, input_file) in &input_files {
-                let day_index = path
-                    .fragment_index("day")
-                    .ok_or(Error::ResolvePath(input_file.to_path_buf()))?;
-
-                let part_index = path
-                    .fragment_index("part")
-                    .ok_or(Error::ResolvePath(input_file.to_path_buf()))?;
-
-                let name = day::name(day_index).unwrap_or("Unknown");
-
-                let start = time::Instant::now();
-
-                match self {
-                    Command::Solve(_) => {
-                        let result = day::solve(input_file, day_index, part_index)
-                            .map_err(|e| Error::SolverError(input_file.to_path_buf(), e))?;
-
-                        println!(
-                            "Solved Day {} ({}) - Part {} [{:?}] -> {}   [{:?}]",
-                            day_index,
-                            name,
-                            part_index,
-                            input_file,
-                            result,
-                            start.elapsed()
-                        );
+    /// Resolves the input files of the selected puzzles into the jobs to run, in a stable order.
+    /// Files of unknown puzzle or type are added to `skipped`
+    fn jobs(&self, input_dir: &Path, skipped: &mut Vec<PathBuf>) -> Result<Vec<Job>> {
+        let args = self.args();
+
+        let input_files = match &args.file {
+            Some(file) => vec![(args.path.clone(), file.to_path_buf())],
+            None => self.resolve_input_files(input_dir, skipped)?,
+        };
+
+        let mut jobs = Vec::new();
+
+        for (path, input_file) in &input_files {
+            let day = path
+                .fragment_index("day")
+                .ok_or(Error::ResolvePath(input_file.to_path_buf()))?;
+
+            match path.fragment_index("part") {
+                Some(part) => jobs.push(Job {
+                    day,
+                    part,
+                    input_file: input_file.to_path_buf(),
+                    example: None,
+                }),
+                // A fetched input is shared by every part of its day, unless a part has its own
+                None if get_file_type(path) == Some(FileType::Input) => {
+                    let has_own_input = |part| {
+                        input_files.iter().any(|(p, _)| {
+                            p.fragment_index("day") == Some(day)
+                                && p.fragment_index("part") == Some(part)
+                                && get_file_type(p) == Some(FileType::Input)
+                        })
+                    };
+
+                    for &part in day::PARTS {
+                        if args.path.selects("part", part) && !has_own_input(part) {
+                            jobs.push(Job {
+                                day,
+                                part,
+                                input_file: input_file.to_path_buf(),
+                                example: None,
+                            });
+                        }
                     }
-                    Command::Test(_) => {
-                        match day::test(input_file, day_index, part_index) {
-                            Ok(result) => {
-                                println!(
-                                    "Test - Day {} ({}) - Part {} [{:?}]   [OK]  ({})   [{:?}]",
-                                    day_index,
-                                    name,
-                                    part_index,
-                                    input_file,
-                                    result,
-                                    start.elapsed()
-                                );
-                            }
-                            Err(e) => {
-                                println!("Test - Day {} ({}) - Part {} [{:?}]   [FAILED]  ({:?})   [{:?}]", day_index, name, part_index, input_file, e, start.elapsed());
-                            }
+                }
+                None => return Err(Error::ResolvePath(input_file.to_path_buf())),
+            }
+        }
+
+        // Embedded examples are tested unless a file is asked for, they can be told apart by their
+        // index within their part as in `day1/part2/example1`
+        let files_only = args.file.is_some()
+            || args.path.fragment("test").is_some()
+            || args.path.fragment("input").is_some();
+
+        if matches!(self, Self::Test(..)) && !files_only {
+            for entry in day::registry().iter().filter(|e| e.year == day::YEAR) {
+                if !args.path.selects("day", entry.day) {
+                    continue;
+                }
+
+                for &part in day::PARTS {
+                    let examples = day::examples(entry.day).iter().filter(|e| e.1 == part);
+
+                    for (example, index) in examples.zip(1..) {
+                        if args.path.selects("part", part) && args.path.selects("example", index) {
+                            jobs.push(Job {
+                                day: entry.day,
+                                part,
+                                input_file: PathBuf::from(format!(
+                                    "{}.rs#part{}.example{}",
+                                    entry.module, part, index
+                                )),
+                                example: Some(*example),
+                            });
                         }
                     }
+                }
+            }
+        }
+
+        // Directory listing order is platform-dependent, sort the jobs to keep the output stable
+        jobs.sort_by(|a, b| (a.day, a.part, &a.input_file).cmp(&(b.day, b.part, &b.input_file)));
+
+        Ok(jobs)
+    }
+
+    pub(super) fn run(&self) -> Result<Summary> {
+        match self {
+            Command::Help(topic) => {
+                println!("{}", cli::usage(topic.as_deref()));
+                return Ok(Summary::default());
+            }
+            Command::Version => {
+                println!("{} {}", cli::NAME, cli::VERSION);
+                return Ok(Summary::default());
+            }
+            _ => {}
+        }
+
+        let current_dir = std::env::current_dir().map_err(Error::CurrentDirectory)?;
+        let config = Config::discover(&current_dir).map_err(Error::Config)?;
+
+        match self {
+            Command::List(list) => {
+                Self::run_list(&config.input_dir(list.input_dir.as_deref()))?;
+                return Ok(Summary::default());
+            }
+            Command::New(new) => {
+                let input_dir = config.input_dir(new.input_dir.as_deref());
+                let source_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("src")
+                    .join("day");
+
+                let files = scaffold::new_day(&source_dir, &input_dir, new.day, &new.name)
+                    .map_err(Error::Scaffold)?;
+
+                for file in files {
+                    println!("Wrote {}", file.display());
+                }
+
+                return Ok(Summary::default());
+            }
+            Command::Fetch(fetch) => {
+                Self::run_fetch(&config, fetch)?;
+                return Ok(Summary::default());
+            }
+            _ => {}
+        }
+
+        let args = self.args();
+        let input_dir = config.input_dir(args.input_dir.as_deref());
+        let input_dir = input_dir.as_path();
+
+        let workers = args
+            .jobs
+            .or(config.jobs.map(NonZeroUsize::get))
+            .unwrap_or_else(num_cpus::get);
+
+        let timeouts = config.timeouts(args.timeout);
+
+        if args.watch {
+            return self.run_watch(input_dir, workers, &timeouts);
+        }
+
+        let mut skipped = Vec::new();
+        let mut jobs = self.jobs(input_dir, &mut skipped)?;
+        warn_skipped(&skipped);
+
+        // Reports are still written when nothing is selected, so that they stay machine readable
+        if jobs.is_empty() {
+            eprintln!("Could not find any input files for {}", args.path.value);
+            if matches!(self, Command::Bench(..) | Command::Submit(..)) {
+                return Ok(Summary::default());
+            }
+        }
+
+        // The standard input can only be read once, keep it around for every part it is run with
+        let stdin = match &args.file {
+            Some(file) if file == Path::new(STDIN) => {
+                let mut data = Vec::new();
+                io::stdin()
+                    .read_to_end(&mut data)
+                    .map_err(Error::ReadStdin)?;
+                Some(data)
+            }
+            _ => None,
+        };
+        let stdin = stdin.as_deref();
+
+        match self {
+            Command::Bench(_, bench) => return Ok(Self::run_bench(&jobs, stdin, bench)),
+            Command::Submit(_, submit) => {
+                Self::run_submit(&config, input_dir, &jobs, stdin, &timeouts, submit)?;
+                return Ok(Summary::default());
+            }
+            _ => {}
+        }
+
+        let kind = self.kind();
+
+        let runner = Runner::load(kind, input_dir, timeouts)?;
+
+        // There is nothing to verify an input against until its answer has been recorded
+        if kind == report::Kind::Verify {
+            jobs.retain(|job| {
+                let recorded = matches!(runner.expected(job, stdin), Ok(Some(_)));
+                if !recorded {
+                    eprintln!(
+                        "WARN no recorded answer for Day {} - Part {} [{:?}]",
+                        job.day, job.part, job.input_file
+                    );
+                }
+                recorded
+            });
+        }
+
+        let format = args.format.or(config.format).unwrap_or(Format::Text);
+
+        let mut reporter = report::new(format);
+        reporter.begin(jobs.len());
+
+        let (junit, fail_fast) = match self {
+            Command::Test(_, test) => (test.junit.as_ref(), test.fail_fast),
+            _ => (None, false),
+        };
+
+        let mut records = Vec::new();
+        let mut summary = Summary {
+            kind: Some(kind),
+            ..Summary::default()
+        };
+
+        pool::run_ordered(
+            &jobs,
+            workers,
+            |job| runner.run(job, stdin),
+            |job, run| {
+                let record = runner.record(job, &run);
+                reporter.record(&record);
+                summary.add(&record);
+
+                let stop = fail_fast && record.status != report::Status::Ok;
+
+                if junit.is_some() {
+                    records.push(record);
+                }
+
+                if let Err(day::SolverError::InputFile(..)) = run.result {
+                    summary.input_errors += 1;
+                }
+
+                if stop {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+
+        summary.skipped = jobs.len() - summary.total.total();
+
+        if kind != report::Kind::Solve {
+            reporter.summary(&summary);
+        }
+
+        reporter.finish();
+
+        if let Some(path) = junit {
+            junit::write(path, &records).map_err(|e| Error::WriteReport(path.to_path_buf(), e))?;
+        }
+
+        Ok(summary)
+    }
+
+    fn kind(&self) -> report::Kind {
+        match self {
+            Command::Solve(_) => report::Kind::Solve,
+            Command::Test(..) => report::Kind::Test,
+            Command::Verify(_) => report::Kind::Verify,
+            _ => unreachable!("{:?} does not report records", self),
+        }
+    }
+
+    /// Re-runs the puzzles whose input files changed until interrupted, only showing what changed
+    /// since the previous run. New input files are picked up as the input directory is listed
+    /// again on every poll.
+    fn run_watch(&self, input_dir: &Path, workers: usize, timeouts: &Timeouts) -> Result<Summary> {
+        let mut watcher = Watcher::default();
+        let mut last: BTreeMap<(usize, usize, PathBuf), report::Record> = BTreeMap::new();
+        let mut first = true;
+
+        loop {
+            // Skipped files are only warned about once the screen is redrawn, not on every poll
+            let mut skipped = Vec::new();
+            let jobs = self.jobs(input_dir, &mut skipped)?;
+
+            // Test fixtures also change with the sidecar file holding their answer
+            let watched: Vec<(&Job, Vec<PathBuf>)> = jobs
+                .iter()
+                .map(|job| {
+                    let mut files = vec![job.input_file.to_path_buf()];
+                    if matches!(self, Self::Test(..)) && job.example.is_none() {
+                        files.push(day::sidecar(&job.input_file));
+                    }
+                    (job, files)
+                })
+                .collect();
+
+            let changed = watcher.poll(
+                watched
+                    .iter()
+                    .flat_map(|(_, f)| f.iter().map(PathBuf::as_path)),
+            );
+
+            let key = |job: &Job| (job.day, job.part, job.input_file.to_path_buf());
+            let removed: Vec<_> = last
+                .keys()
+                .filter(|&k| !jobs.iter().any(|j| key(j) == *k))
+                .cloned()
+                .collect();
+
+            if !first && changed.is_empty() && removed.is_empty() {
+                thread::sleep(watch::INTERVAL);
+                continue;
+            }
+
+            first = false;
+
+            let affected: Vec<&Job> = watched
+                .iter()
+                .filter(|(_, files)| files.iter().any(|f| changed.contains(f)))
+                .map(|&(job, _)| job)
+                .collect();
+
+            let runner = Runner::load(self.kind(), input_dir, timeouts.clone())?;
+            let mut records = Vec::new();
+
+            pool::run_ordered(
+                &affected,
+                workers,
+                |job| runner.run(job, None),
+                |job, run| {
+                    records.push(runner.record(job, &run));
+                    ControlFlow::Continue(())
+                },
+            );
+
+            watch::clear_screen();
+            println!(
+                "Watching {} puzzles in {:?}, press Ctrl-C to stop",
+                jobs.len(),
+                input_dir
+            );
+            warn_skipped(&skipped);
+            println!();
+
+            let mut unchanged = jobs.len() - affected.len();
+
+            for record in records {
+                let key = (record.day, record.part, record.input_file.to_path_buf());
+                let outcome = record.outcome();
+
+                match last.get(&key).map(report::Record::outcome) {
+                    None => println!("+ {}   {}", record.title(), outcome),
+                    Some(before) if before == outcome => unchanged += 1,
+                    Some(before) => println!("~ {}   {} -> {}", record.title(), before, outcome),
+                }
+
+                last.insert(key, record);
+            }
+
+            for key in removed {
+                if let Some(record) = last.remove(&key) {
+                    println!("- {}", record.title());
+                }
+            }
+
+            if unchanged > 0 {
+                println!("{} unchanged", unchanged);
+            }
+
+            thread::sleep(watch::INTERVAL);
+        }
+    }
+
+    /// Benchmarks every job, a job failing being reported in place of its timings and counted as
+    /// an error of the summary
+    fn run_bench(jobs: &[Job], stdin: Option<&[u8]>, args: &BenchArgs) -> Summary {
+        let mut summary = Summary::default();
+
+        // Runs are timed one after the other so that they don't compete with each other for
+        // CPU time and memory bandwidth
+        for job in jobs {
+            let name = day::name(job.day).unwrap_or("Unknown");
+
+            let bench = match day::bench(
+                job.input(stdin),
+                job.day,
+                job.part,
+                args.warmup,
+                args.iterations,
+            ) {
+                Ok(bench) => bench,
+                Err(e) => {
+                    println!(
+                        "Failed Day {} ({}) - Part {} [{:?}] -> {}",
+                        job.day,
+                        name,
+                        job.part,
+                        job.input_file,
+                        describe(&e)
+                    );
+                    if let Some(snippet) = e.snippet() {
+                        print!("{}", snippet);
+                    }
+
+                    summary.total.errors += 1;
+                    if let day::SolverError::InputFile(..) = e {
+                        summary.input_errors += 1;
+                    }
+                    continue;
+                }
+            };
+
+            if let Some(stats) = Stats::from_samples(bench.samples) {
+                println!(
+                    "Bench Day {} ({}) - Part {} [{:?}]   min {:?}  mean {:?}  median {:?}  p95 {:?}  stddev {:?}   [{} iterations]",
+                    job.day,
+                    name,
+                    job.part,
+                    job.input_file,
+                    stats.min,
+                    stats.mean,
+                    stats.median,
+                    stats.p95,
+                    stats.stddev,
+                    stats.iterations
+                );
+            }
+
+            if let Some(memory) = bench.memory {
+                println!("    {}", memory);
+            }
+
+            summary.total.passed += 1;
+        }
+
+        summary
+    }
+
+    fn run_list(input_dir: &Path) -> Result<()> {
+        let mut files = read_input_files(input_dir)?;
+        files.sort();
+
+        let mut inputs: BTreeMap<(usize, usize), Vec<PathBuf>> = BTreeMap::new();
+        let mut tests: BTreeMap<(usize, usize), Vec<PathBuf>> = BTreeMap::new();
+        let mut skipped = Vec::new();
+
+        for file in files {
+            let path = match ArgPath::parse_path(&file) {
+                Ok(path) => path,
+                Err(_) => {
+                    skipped.push(file);
+                    continue;
+                }
+            };
+
+            let day = path.fragment_index("day");
+            let part = path.fragment_index("part");
+
+            match (get_file_type(&path), day, part) {
+                (Some(FileType::Input), Some(day), Some(part)) => {
+                    inputs.entry((day, part)).or_default().push(file)
+                }
+                (Some(FileType::Input), Some(day), None) => {
+                    for &part in day::PARTS {
+                        inputs.entry((day, part)).or_default().push(file.clone());
+                    }
+                }
+                (Some(FileType::Test), Some(day), Some(part)) => {
+                    tests.entry((day, part)).or_default().push(file)
+                }
+                _ => skipped.push(file),
+            }
+        }
+
+        let file_names = |files: Option<&Vec<PathBuf>>| match files {
+            Some(files) => files
+                .iter()
+                .filter_map(|f| f.file_name())
+                .map(|f| f.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => "missing".to_string(),
+        };
+
+        for entry in day::registry().iter() {
+            let day = entry.day;
+            println!(
+                "{} Day {} ({}) [{}]",
+                entry.year,
+                day,
+                entry.name(),
+                entry.module
+            );
+
+            for &part in day::PARTS {
+                let expected = day::expected(day, part).filter(|e| !e.is_empty());
+                let examples = day::examples(day).iter().filter(|e| e.1 == part).count();
+
+                // Embedded examples are enough to test a part without any example file
+                let tests = match (examples, tests.get(&(day, part))) {
+                    (0, files) => file_names(files),
+                    (examples, None) => format!("{} embedded", examples),
+                    (examples, files) => format!("{} embedded, {}", examples, file_names(files)),
                 };
+
+                println!(
+                    "  Part {}   input: {}   test: {}   expected: {}",
+                    part,
+                    file_names(inputs.get(&(day, part))),
+                    tests,
+                    expected.unwrap_or("missing")
+                );
             }
         }
 
+        if !skipped.is_empty() {
+            println!();
+            println!("Skipped files in {:?}:", input_dir);
+
+            for file in &skipped {
+                println!("  {}", file.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_fetch(config: &Config, fetch: &FetchArgs) -> Result<()> {
+        let input_dir = config.input_dir(fetch.input_dir.as_deref());
+        let input_file = input_dir.join(format!("day{}.input.txt", fetch.day));
+
+        // The empty placeholder created by `new` is not a cached input
+        let cached = fs::metadata(&input_file).is_ok_and(|m| m.len() > 0);
+        if cached {
+            println!("Using cached input {}", input_file.display());
+            return Ok(());
+        }
+
+        let session = client::session(
+            fetch.session_file.as_deref(),
+            config.session_file.as_deref(),
+        )
+        .map_err(Error::Client)?;
+        let base_url = client::base_url(fetch.base_url.as_deref(), config.base_url.as_deref());
+        let client = Client::new(&base_url, session);
+
+        let input = client.input(day::YEAR, fetch.day).map_err(Error::Client)?;
+
+        // Write to a temporary file first so that an interrupted write never ends up cached
+        let partial = input_file.with_extension("txt.part");
+        fs::create_dir_all(input_dir)
+            .and_then(|_| fs::write(&partial, input))
+            .and_then(|_| fs::rename(&partial, &input_file))
+            .map_err(|e| Error::WriteInput(input_file.to_path_buf(), e))?;
+
+        println!(
+            "Fetched input of day {} into {}",
+            fetch.day,
+            input_file.display()
+        );
+        Ok(())
+    }
+
+    fn run_submit(
+        config: &Config,
+        input_dir: &Path,
+        jobs: &[Job],
+        stdin: Option<&[u8]>,
+        timeouts: &Timeouts,
+        submit: &SubmitArgs,
+    ) -> Result<()> {
+        let job = match jobs {
+            [job] => job,
+            _ => {
+                return Err(Error::AmbiguousInput(
+                    jobs.iter().map(|j| j.input_file.to_path_buf()).collect(),
+                ))
+            }
+        };
+
+        let answer = day::solve(job.input(stdin), job.day, job.part, timeouts.get(job.day))
+            .0
+            .map_err(|e| Error::Solver(job.input_file.to_path_buf(), e))?;
+
+        let base_url = client::base_url(submit.base_url.as_deref(), config.base_url.as_deref());
+
+        let database_path = Database::path(input_dir);
+        let mut database = Database::load(&database_path).map_err(Error::Submission)?;
+
+        let warnings = database.check(day::YEAR, job.day, job.part, &answer);
+        let wait = database.wait();
+
+        if submit.dry_run {
+            println!(
+                "Would submit Day {} - Part {} [{:?}] -> {}",
+                job.day, job.part, job.input_file, answer
+            );
+            for warning in &warnings {
+                println!("WARN {}", warning);
+            }
+            if let Some(wait) = wait {
+                println!("WARN wait {:?} before submitting again", wait);
+            }
+            println!(
+                "POST {}{} level={}&answer={}",
+                base_url.trim_end_matches('/'),
+                client::answer_path(day::YEAR, job.day),
+                job.part,
+                answer
+            );
+
+            return Ok(());
+        }
+
+        if !submit.force {
+            if !warnings.is_empty() {
+                return Err(Error::ImpossibleAnswer(answer, warnings));
+            }
+
+            if let Some(wait) = wait {
+                return Err(Error::RateLimited(wait));
+            }
+        }
+
+        let session = client::session(
+            submit.session_file.as_deref(),
+            config.session_file.as_deref(),
+        )
+        .map_err(Error::Client)?;
+        let client = Client::new(&base_url, session);
+
+        let page = client
+            .answer(day::YEAR, job.day, job.part, &answer)
+            .map_err(Error::Client)?;
+        let feedback = Feedback::parse(&page);
+
+        database.record(Submission::new(
+            day::YEAR,
+            job.day,
+            job.part,
+            &answer,
+            &feedback,
+        ));
+        database.save(&database_path).map_err(Error::Submission)?;
+
+        println!(
+            "Submitted Day {} - Part {} [{:?}] -> {}   [{}]",
+            job.day, job.part, job.input_file, answer, feedback.outcome
+        );
+
+        if let Some(wait) = feedback.wait {
+            println!("Wait {:?} before submitting again", wait);
+        }
+
+        if submit.record && feedback.outcome == Outcome::Correct {
+            let answers_path = Answers::path(input_dir);
+            let mut answers = Answers::load(&answers_path).map_err(Error::Answers)?;
+            answers.set(day::YEAR, job.day, job.part, &job.input_file, &answer);
+            answers.save(&answers_path).map_err(Error::Answers)?;
+
+            println!("Recorded answer in {}", answers_path.display());
+        }
+
+        if !feedback.message.is_empty() {
+            println!("{}", feedback.message);
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::day::Timeout;
+use crate::report::Format;
+
+/// Name of the config file, looked up in the current directory and its ancestors
+pub(super) const CONFIG_FILE: &str = "aoc.toml";
+
+pub(super) const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+pub(super) const DEFAULT_INPUT_DIR: &str = "inputs";
+
+#[derive(Debug, Error)]
+pub(super) enum Error {
+    #[error("failed to read {0:?}")]
+    Read(PathBuf, #[source] io::Error),
+
+    #[error("failed to parse {0:?}")]
+    Parse(PathBuf, #[source] toml::de::Error),
+
+    #[error("invalid day `{1}` in the timeouts of {0:?}, expected a key like `day5`")]
+    TimeoutDay(PathBuf, String),
+}
+
+/// Defaults for the options that are not given on the command line
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(super) struct Config {
+    input_dir: Option<PathBuf>,
+
+    pub(super) format: Option<Format>,
+    pub(super) jobs: Option<NonZeroUsize>,
+
+    timeout: Option<Timeout>,
+
+    /// Timeouts of the days that need more or less time than the others, keyed like `day5`
+    timeouts: BTreeMap<String, Timeout>,
+
+    pub(super) base_url: Option<String>,
+    pub(super) session_file: Option<PathBuf>,
+}
+
+impl Config {
+    /// Loads the closest config file from `dir` upwards, or the defaults if there is none
+    pub(super) fn discover(dir: &Path) -> Result<Self, Error> {
+        match dir
+            .ancestors()
+            .map(|d| d.join(CONFIG_FILE))
+            .find(|p| p.is_file())
+        {
+            // Keep paths relative when the config file is in `dir` itself, for shorter output
+            Some(path) => Self::load(path.strip_prefix(dir).unwrap_or(&path)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path).map_err(|e| Error::Read(path.to_path_buf(), e))?;
+        let mut config: Self =
+            toml::from_str(&contents).map_err(|e| Error::Parse(path.to_path_buf(), e))?;
+
+        // Paths are relative to the config file so that it works from any subdirectory
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for path in [&mut config.input_dir, &mut config.session_file]
+            .into_iter()
+            .flatten()
+        {
+            *path = dir.join(&*path);
+        }
+
+        if let Some(day) = config.timeouts.keys().find(|d| timeout_day(d).is_none()) {
+            return Err(Error::TimeoutDay(path.to_path_buf(), day.clone()));
+        }
+
+        Ok(config)
+    }
+
+    /// Resolves the input directory, `input_dir` taking precedence over the environment and then
+    /// over the config file
+    pub(super) fn input_dir(&self, input_dir: Option<&Path>) -> PathBuf {
+        input_dir
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(INPUT_DIR_ENV).map(PathBuf::from))
+            .or_else(|| self.input_dir.clone())
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+    }
+
+    /// Resolves the timeouts of the solvers, `timeout` replacing every timeout of the config file
+    pub(super) fn timeouts(&self, timeout: Option<Duration>) -> Timeouts {
+        match timeout {
+            Some(timeout) => Timeouts {
+                default: Some(timeout),
+                days: BTreeMap::new(),
+            },
+            None => Timeouts {
+                default: self.timeout.map(|t| t.0),
+                days: self
+                    .timeouts
+                    .iter()
+                    .filter_map(|(day, t)| Some((timeout_day(day)?, t.0)))
+                    .collect(),
+            },
+        }
+    }
+}
+
+fn timeout_day(key: &str) -> Option<usize> {
+    key.strip_prefix("day")?
+        .parse::<NonZeroUsize>()
+        .ok()
+        .map(NonZeroUsize::get)
+}
+
+/// How long the solver of each day may run, if limited at all
+#[derive(Debug, Default, Clone)]
+pub(super) struct Timeouts {
+    default: Option<Duration>,
+    days: BTreeMap<usize, Duration>,
+}
+
+impl Timeouts {
+    pub(super) fn get(&self, day: usize) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.default)
+    }
+}
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use super::{Solver, SolverError, SolverResult};
+use super::{Example, Solver, SolverError, SolverResult};
 
 use std::{result::Result, str::FromStr};
 
@@ -37,25 +37,29 @@ impl Elf {
     }
 }
 
-impl TryFrom<Vec<String>> for Elf {
-    type Error = Error;
+impl TryFrom<Vec<(usize, String)>> for Elf {
+    type Error = SolverError;
 
-    fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
+    fn try_from(value: Vec<(usize, String)>) -> Result<Self, Self::Error> {
         let rations: Vec<Ration> = value
             .into_iter()
-            .map(|s| s.parse())
+            .map(|(index, s)| {
+                s.parse()
+                    .map_err(|e| super::parse_error(index, &s, 0..s.len(), e))
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { rations })
     }
 }
 
-fn partition(lines: Vec<String>) -> Vec<Vec<String>> {
+/// Splits the lines into the rations of each elf, keeping the index of every line
+fn partition(lines: Vec<String>) -> Vec<Vec<(usize, String)>> {
     let mut partitions = Vec::new();
     let mut current = Vec::new();
 
-    for item in lines.into_iter() {
-        if item.is_empty() {
+    for item in lines.into_iter().enumerate() {
+        if item.1.is_empty() {
             partitions.push(current.clone());
             current.clear();
         } else {
@@ -70,13 +74,31 @@ fn partition(lines: Vec<String>) -> Vec<Vec<String>> {
     partitions
 }
 
-fn read_elfs(lines: Vec<String>) -> Result<Vec<Elf>, Error> {
+fn read_elfs(lines: Vec<String>) -> Result<Vec<Elf>, SolverError> {
     partition(lines)
         .into_iter()
         .map(Elf::try_from)
         .collect::<Result<Vec<_>, _>>()
 }
 
+/// The example of the puzzle description, shared by both parts
+const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
 struct Day1;
 
 impl Solver for Day1 {
@@ -85,7 +107,9 @@ impl Solver for Day1 {
     }
 
     fn solve_part1(&self, lines: Vec<String>) -> SolverResult {
-        let elfs = read_elfs(lines).map_err(|e| SolverError::Generic(e.into()))?;
+        let elfs = read_elfs(lines)?;
+        super::parsed();
+
         Ok(elfs
             .iter()
             .map(|e| e.total_calories())
@@ -95,8 +119,10 @@ impl Solver for Day1 {
     }
 
     fn solve_part2(&self, lines: Vec<String>) -> SolverResult {
-        let mut elfs = read_elfs(lines).map_err(|e| SolverError::Generic(e.into()))?;
-        elfs.sort_by(|e1, e2| e2.total_calories().cmp(&e1.total_calories()));
+        let mut elfs = read_elfs(lines)?;
+        super::parsed();
+
+        elfs.sort_by_key(|e| std::cmp::Reverse(e.total_calories()));
 
         let top_three = elfs
             .get(0..3)
@@ -117,6 +143,10 @@ impl Solver for Day1 {
             _ => unreachable!(),
         }
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[(EXAMPLE, 1, "24000"), (EXAMPLE, 2, "45000")]
+    }
 }
 
 pub(super) fn new() -> Box<dyn Solver> {
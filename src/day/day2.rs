@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use thiserror::Error;
 
-use super::{Solver, SolverError};
+use super::{Example, Solver, SolverError, Spanned};
 
 #[derive(Debug, Error)]
 enum Error {
@@ -75,6 +75,30 @@ trait Round {
     fn play(&self) -> usize;
 }
 
+/// Parses the two plays of a round, pointing at the one that is invalid
+fn parse_plays<A, B>(s: &str) -> Result<(A, B), Spanned<Error>>
+where
+    A: FromStr<Err = Error>,
+    B: FromStr<Err = Error>,
+{
+    let invalid = || Spanned::new(Error::InvalidRound(s.to_string()), 0..s.len());
+
+    let mut parts = s.split(" ");
+
+    let player_1 = parts.next().ok_or_else(invalid)?;
+    let player_2 = parts.next().ok_or_else(invalid)?;
+    let offset = player_1.len() + 1;
+
+    Ok((
+        player_1
+            .parse()
+            .map_err(|e| Spanned::new(e, 0..player_1.len()))?,
+        player_2
+            .parse()
+            .map_err(|e| Spanned::new(e, offset..offset + player_2.len()))?,
+    ))
+}
+
 struct Round1(Deck, Deck);
 
 struct Round2(Deck, Outcome);
@@ -104,15 +128,12 @@ impl Round for Round1 {
 }
 
 impl FromStr for Round1 {
-    type Err = Error;
+    type Err = Spanned<Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(" ");
+        let (player_1, player_2) = parse_plays(s)?;
 
-        let player_1 = parts.next().ok_or(Error::InvalidRound(s.to_string()))?;
-        let player_2 = parts.next().ok_or(Error::InvalidRound(s.to_string()))?;
-
-        Ok(Round1(player_1.parse()?, player_2.parse()?))
+        Ok(Round1(player_1, player_2))
     }
 }
 
@@ -137,15 +158,12 @@ impl Round for Round2 {
 }
 
 impl FromStr for Round2 {
-    type Err = Error;
+    type Err = Spanned<Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(" ");
+        let (player_1, player_2) = parse_plays(s)?;
 
-        let player_1 = parts.next().ok_or(Error::InvalidRound(s.to_string()))?;
-        let player_2 = parts.next().ok_or(Error::InvalidRound(s.to_string()))?;
-
-        Ok(Round2(player_1.parse()?, player_2.parse()?))
+        Ok(Round2(player_1, player_2))
     }
 }
 
@@ -159,28 +177,32 @@ impl<R: Round> StrategyGuide<R> {
 
 impl<R: Round + FromStr> TryFrom<Vec<String>> for StrategyGuide<R>
 where
-    R: FromStr<Err = Error>,
+    R: FromStr<Err = Spanned<Error>>,
 {
-    type Error = Error;
+    type Error = SolverError;
 
     fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-        let rounds: Vec<R> = value
-            .into_iter()
-            .map(|v| v.parse())
-            .collect::<Result<Vec<_>, _>>()?;
+        let rounds: Vec<R> = super::parse_lines(&value, str::parse)?;
         Ok(Self(rounds))
     }
 }
 
+/// The example of the puzzle description, shared by both parts
+const EXAMPLE: &str = "\
+A Y
+B X
+C Z
+";
+
 struct Day2;
 
 fn solve<R: Round>(lines: Vec<String>) -> super::SolverResult
 where
-    StrategyGuide<R>: TryFrom<Vec<String>>,
-    <StrategyGuide<R> as TryFrom<Vec<String>>>::Error: std::error::Error + 'static,
+    StrategyGuide<R>: TryFrom<Vec<String>, Error = SolverError>,
 {
-    let strategy_guide =
-        StrategyGuide::<R>::try_from(lines).map_err(|e| SolverError::Generic(e.into()))?;
+    let strategy_guide = StrategyGuide::<R>::try_from(lines)?;
+    super::parsed();
+
     Ok(strategy_guide.evaluate().to_string())
 }
 
@@ -204,6 +226,10 @@ impl Solver for Day2 {
             _ => unreachable!(),
         }
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[(EXAMPLE, 1, "15"), (EXAMPLE, 2, "12")]
+    }
 }
 
 pub(super) fn new() -> Box<dyn Solver> {
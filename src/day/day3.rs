@@ -2,7 +2,7 @@ use std::{collections::HashSet, str::FromStr};
 
 use thiserror::Error;
 
-use super::{Solver, SolverError};
+use super::{Example, Solver, Spanned};
 
 #[derive(Debug, Error)]
 enum Error {
@@ -11,7 +11,7 @@ enum Error {
 }
 
 fn distance(c1: char, c2: char) -> usize {
-    (c2 as isize - c1 as isize).abs() as usize
+    (c2 as isize - c1 as isize).unsigned_abs()
 }
 
 const TOTAL_COMPARTMENTS: usize = 2;
@@ -50,12 +50,12 @@ impl Compartment {
 }
 
 impl FromStr for Compartment {
-    type Err = Error;
+    type Err = Spanned<Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(
-            s.chars()
-                .map(Item::try_from)
+            s.char_indices()
+                .map(|(i, c)| Item::try_from(c).map_err(|e| Spanned::new(e, i..i + c.len_utf8())))
                 .collect::<Result<HashSet<_>, _>>()?,
         ))
     }
@@ -77,7 +77,7 @@ impl Rucksack {
 }
 
 impl FromStr for Rucksack {
-    type Err = Error;
+    type Err = Spanned<Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let len = s.len();
@@ -85,10 +85,23 @@ impl FromStr for Rucksack {
         let first_half = &s[..half];
         let second_half = &s[half..];
 
-        Ok(Self([first_half.parse()?, second_half.parse()?]))
+        Ok(Self([
+            first_half.parse()?,
+            second_half.parse().map_err(|e: Spanned<_>| e.shift(half))?,
+        ]))
     }
 }
 
+/// The example of the puzzle description, shared by both parts
+const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
 struct Day3;
 
 impl Solver for Day3 {
@@ -97,11 +110,8 @@ impl Solver for Day3 {
     }
 
     fn solve_part1(&self, lines: Vec<String>) -> super::SolverResult {
-        let rucksacks: Vec<Rucksack> = lines
-            .into_iter()
-            .map(|l| l.parse())
-            .collect::<Result<Vec<_>, Error>>()
-            .map_err(|e| SolverError::Generic(e.into()))?;
+        let rucksacks: Vec<Rucksack> = super::parse_lines(&lines, str::parse)?;
+        super::parsed();
 
         let common_items = rucksacks
             .iter()
@@ -114,27 +124,24 @@ impl Solver for Day3 {
     }
 
     fn solve_part2(&self, lines: Vec<String>) -> super::SolverResult {
-        let rucksacks: Vec<Rucksack> = lines
-            .into_iter()
-            .map(|l| l.parse())
-            .collect::<Result<Vec<_>, Error>>()
-            .map_err(|e| SolverError::Generic(e.into()))?;
-
-        let groups = rucksacks.as_slice().chunks(3);
-        Ok(groups
-            .into_iter()
-            .filter_map(|g| {
-                let first = g[0].items();
-                let second = g[1].items();
-                let third = g[2].items();
-
-                let common = &first & &second;
-                let badge = &common & &third;
-
-                badge.iter().next().and_then(Item::priority)
-            })
-            .sum::<usize>()
-            .to_string())
+        let rucksacks: Vec<Rucksack> = super::parse_lines(&lines, str::parse)?;
+        super::parsed();
+
+        let mut priorities = 0;
+        for g in rucksacks.chunks(3) {
+            super::check_cancelled()?;
+
+            let first = g[0].items();
+            let second = g[1].items();
+            let third = g[2].items();
+
+            let common = &first & &second;
+            let badge = &common & &third;
+
+            priorities += badge.iter().next().and_then(Item::priority).unwrap_or_default();
+        }
+
+        Ok(priorities.to_string())
     }
 
     fn test_expected(&self, part: usize) -> &'static str {
@@ -144,6 +151,10 @@ impl Solver for Day3 {
             _ => unreachable!(),
         }
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[(EXAMPLE, 1, "157"), (EXAMPLE, 2, "70")]
+    }
 }
 
 pub(super) fn new() -> Box<dyn Solver> {
@@ -8,27 +8,196 @@ use std::vec::Vec;
 use std::error::Error;
 use std::result::Result;
 
+use std::cell::{Cell, RefCell};
 use std::iter::Iterator;
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once, OnceLock};
+use std::time::{Duration, Instant};
+use std::{panic, thread};
 
-mod day1;
-mod day2;
-mod day3;
+use serde::Deserialize;
 
-#[derive(Debug)]
+mod registry;
+
+use crate::alloc;
+
+pub(super) use registry::Registry;
+
+registry::solvers! {
+    2022 => {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+    }
+}
+
+/// The year the puzzles of this crate belong to
+pub(super) const YEAR: u16 = 2022;
+
+#[derive(Debug, thiserror::Error)]
 pub(super) enum SolverError {
+    #[error("no solver is registered for day {0}")]
     UnknownDay(usize),
+
+    #[error("part {0} does not exist, expected 1 or 2")]
     InvalidPart(usize),
 
-    InputFile(PathBuf, std::io::Error),
+    #[error("failed to read {0:?}")]
+    InputFile(PathBuf, #[source] std::io::Error),
 
-    Generic(Box<dyn Error>),
+    #[error("{0}")]
+    Generic(Box<dyn Error + Send + Sync>),
 
+    /// The input has invalid text at `columns` of its `line`, both counted from 1 and the end of
+    /// `columns` being excluded. `text` holds the whole line
+    #[error("invalid input at {}:{line}:{}", path.display(), columns.start)]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        columns: Range<usize>,
+        text: String,
+        #[source]
+        cause: Box<dyn Error + Send + Sync>,
+    },
+
+    #[error("expected {expected}, got {got}")]
     Test { got: String, expected: String },
+
+    /// The solver ran for longer than it was allowed to, holds how long it had been running
+    #[error("timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// The solver noticed it was given up on, see [`check_cancelled`]
+    #[error("cancelled")]
+    Cancelled,
+
+    /// The solver panicked, `location` being the file, line and column it panicked at if known
+    #[error("panicked at {}: {message}", location.as_deref().unwrap_or("an unknown location"))]
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+}
+
+impl SolverError {
+    /// Names the input file in parse errors, which solvers can't do as they only see its lines
+    fn in_file(self, file: &Path) -> Self {
+        match self {
+            SolverError::Parse {
+                line,
+                columns,
+                text,
+                cause,
+                ..
+            } => SolverError::Parse {
+                path: file.to_path_buf(),
+                line,
+                columns,
+                text,
+                cause,
+            },
+            e => e,
+        }
+    }
+
+    /// Shows where a parse error is, with carets under the invalid text:
+    ///
+    /// ```text
+    ///  --> inputs/day1.part1.input.txt:3:1
+    ///   |
+    /// 3 | abc
+    ///   | ^^^
+    /// ```
+    pub(super) fn snippet(&self) -> Option<String> {
+        let SolverError::Parse {
+            path,
+            line,
+            columns,
+            text,
+            ..
+        } = self
+        else {
+            return None;
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+        let carets = "^".repeat(columns.len().max(1));
+        let padding: String = text
+            .chars()
+            .take(columns.start - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        Some(format!(
+            "{gutter}--> {}:{line}:{}\n{gutter} |\n{line} | {text}\n{gutter} | {padding}{carets}\n",
+            path.display(),
+            columns.start
+        ))
+    }
 }
 
-type SolverResult = Result<String, SolverError>;
+pub(super) type SolverResult = Result<String, SolverError>;
+
+/// A [`SolverError::Parse`] about the text at the byte offsets `columns` of the line at `index`
+/// of the input
+pub(super) fn parse_error<E: Into<Box<dyn Error + Send + Sync>>>(
+    index: usize,
+    line: &str,
+    columns: Range<usize>,
+    cause: E,
+) -> SolverError {
+    let column = |offset: usize| line[..offset.min(line.len())].chars().count() + 1;
 
-pub(super) trait Solver {
+    SolverError::Parse {
+        path: PathBuf::new(),
+        line: index + 1,
+        columns: column(columns.start)..column(columns.end),
+        text: line.to_string(),
+        cause: cause.into(),
+    }
+}
+
+/// An error about the text at the byte offsets `columns` of a line
+#[derive(Debug)]
+pub(super) struct Spanned<E> {
+    pub(super) error: E,
+    pub(super) columns: Range<usize>,
+}
+
+impl<E> Spanned<E> {
+    pub(super) fn new(error: E, columns: Range<usize>) -> Self {
+        Self { error, columns }
+    }
+
+    /// Moves the columns by `offset`, for errors about a part of a line
+    pub(super) fn shift(self, offset: usize) -> Self {
+        Self {
+            error: self.error,
+            columns: self.columns.start + offset..self.columns.end + offset,
+        }
+    }
+}
+
+/// Parses every line of the input with `f`, failing with the first line it can't parse
+pub(super) fn parse_lines<T, E, Fn>(lines: &[String], mut f: Fn) -> Result<Vec<T>, SolverError>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+    Fn: FnMut(&str) -> Result<T, Spanned<E>>,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| f(line).map_err(|e| parse_error(index, line, e.columns, e.error)))
+        .collect()
+}
+
+/// An example input embedded in the module of a day, as its text, its part and its answer
+pub(super) type Example = (&'static str, usize, &'static str);
+
+pub(super) trait Solver: Send + Sync {
     fn name(&self) -> &'static str;
 
     fn solve_part1(&self, lines: Vec<String>) -> SolverResult;
@@ -36,35 +205,232 @@ pub(super) trait Solver {
     fn solve_part2(&self, lines: Vec<String>) -> SolverResult;
 
     fn test_expected(&self, part: usize) -> &'static str;
+
+    /// Examples that are tested on top of the example files of the input directory
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+}
+
+/// Every day of the puzzle is split into these parts
+pub(super) const PARTS: &[usize] = &[1, 2];
+
+struct PreparedSolver<'a>(Vec<String>, &'a dyn Solver);
+
+thread_local! {
+    /// When the solver running on this thread was done parsing its input
+    static PARSED: Cell<Option<Instant>> = const { Cell::new(None) };
 }
 
-struct PreparedSolver<'a>(Vec<String>, &'a Box<dyn Solver>);
+/// Marks the end of the parsing phase of the running solver, so that the time spent parsing the
+/// input is reported apart from the time spent solving the puzzle
+pub(super) fn parsed() {
+    PARSED.with(|p| p.set(Some(Instant::now())));
+}
+
+/// Tells the solver running on a thread that it was given up on
+#[derive(Debug, Default, Clone)]
+struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    /// Token of the solver running on this thread, if it can time out
+    static CANCELLATION: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Fails once the running solver timed out. Solvers with long loops should call it every now and
+/// then, as a solver that timed out is otherwise left running in the background until it's done
+pub(super) fn check_cancelled() -> Result<(), SolverError> {
+    let cancelled = CANCELLATION.with_borrow(|c| c.as_ref().is_some_and(|c| c.is_cancelled()));
+
+    if cancelled {
+        Err(SolverError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+/// How long a solver may run before it is given up on, like `500ms`, `10s`, `1.5m` or `30`
+/// seconds
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub(super) struct Timeout(pub(super) Duration);
+
+impl FromStr for Timeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+        let (value, unit) = s.split_at(split);
+
+        let scale = match unit {
+            "ms" => 1e-3,
+            "" | "s" => 1.0,
+            "m" => 60.0,
+            _ => return Err(s.to_string()),
+        };
+
+        value
+            .parse::<f64>()
+            .ok()
+            .and_then(|v| Duration::try_from_secs_f64(v * scale).ok())
+            .filter(|d| !d.is_zero())
+            .map(Self)
+            .ok_or_else(|| s.to_string())
+    }
+}
+
+impl TryFrom<String> for Timeout {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+            .map_err(|s| format!("invalid timeout `{}`, expected a duration like `10s`", s))
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are caught by [`catch_panic`]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+
+    /// Where the last panic caught on this thread happened
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Replaces the panic hook with one that keeps quiet about the panics caught by [`catch_panic`],
+/// only remembering their location, and defers to the previous hook for any other panic
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                let location = info.location().map(ToString::to_string);
+                PANIC_LOCATION.set(location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into a [`SolverError::Panic`] so that the other solvers keep running
+fn catch_panic<T, Fn: FnOnce() -> Result<T, SolverError>>(f: Fn) -> Result<T, SolverError> {
+    install_panic_hook();
+
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    CATCHING.set(catching);
+
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".to_string());
+
+        Err(SolverError::Panic {
+            message,
+            location: PANIC_LOCATION.take(),
+        })
+    })
+}
+
+/// What was measured while running a solver: how long each phase took and how much it allocated
+#[derive(Debug, Default, Copy, Clone)]
+pub(super) struct Metrics {
+    /// Reading the input into lines
+    pub(super) read: Duration,
+
+    /// Parsing the lines, unknown unless the solver calls [`parsed`]
+    pub(super) parse: Option<Duration>,
+
+    /// Solving the puzzle, including the parsing when it is unknown
+    pub(super) solve: Duration,
+
+    /// Heap usage of parsing and solving, only known with the `alloc-stats` feature
+    pub(super) memory: Option<alloc::Stats>,
+}
+
+/// Where the input of a solver is read from
+#[derive(Debug, Copy, Clone)]
+pub(super) enum Input<'a> {
+    File(&'a Path),
+
+    /// Input that was already read, such as the standard input, named by a path in errors
+    Data(&'a Path, &'a [u8]),
+}
+
+impl<'a> Input<'a> {
+    fn path(&self) -> &'a Path {
+        match *self {
+            Input::File(path) | Input::Data(path, _) => path,
+        }
+    }
+
+    fn open(&self) -> Result<Box<dyn BufRead + 'a>, SolverError> {
+        match *self {
+            Input::File(path) => fs::File::open(path)
+                .map(|f| Box::new(BufReader::new(f)) as Box<dyn BufRead>)
+                .map_err(|e| SolverError::InputFile(path.to_path_buf(), e)),
+            Input::Data(_, data) => Ok(Box::new(data)),
+        }
+    }
+}
+
+pub(super) fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| Registry::new(entries()))
+}
+
+fn lookup(day: usize) -> Result<&'static dyn Solver, SolverError> {
+    registry()
+        .get(YEAR, day)
+        .map(|e| e.solver.as_ref())
+        .ok_or(SolverError::UnknownDay(day))
+}
 
 pub(super) fn name(day: usize) -> Option<&'static str> {
-    let days: &[Box<dyn Solver>] = &[day1::new(), day2::new(), day3::new()];
+    lookup(day).ok().map(|s| s.name())
+}
+
+pub(super) fn expected(day: usize, part: usize) -> Option<&'static str> {
+    if PARTS.contains(&part) {
+        lookup(day).ok().map(|s| s.test_expected(part))
+    } else {
+        None
+    }
+}
 
-    days.get(day - 1).map(|d| d.name())
+pub(super) fn examples(day: usize) -> &'static [Example] {
+    lookup(day).map(|s| s.examples()).unwrap_or_default()
 }
 
-fn prepare_solver<P: AsRef<Path>, Fn: FnOnce(PreparedSolver) -> SolverResult>(
-    path: P,
+fn prepare_solver<T, R: BufRead, Fn: FnOnce(PreparedSolver<'static>) -> Result<T, SolverError>>(
+    reader: R,
+    path: &Path,
     day: usize,
     f: Fn,
-) -> SolverResult {
-    let days: &[Box<dyn Solver>] = &[day1::new(), day2::new(), day3::new()];
-
-    let file = fs::File::open(path.as_ref())
-        .map_err(|e| SolverError::InputFile(PathBuf::from(path.as_ref()), e))?;
+) -> Result<T, SolverError> {
+    let solver = lookup(day)?;
 
-    let reader = BufReader::new(file);
     let lines = reader
         .lines()
         .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| SolverError::InputFile(PathBuf::from(path.as_ref()), e))?;
+        .map_err(|e| SolverError::InputFile(path.to_path_buf(), e))?;
 
-    days.get(day - 1)
-        .ok_or(SolverError::UnknownDay(day))
-        .and_then(|s| f(PreparedSolver(lines, s)))
+    f(PreparedSolver(lines, solver)).map_err(|e| e.in_file(path))
 }
 
 fn run_solver<'a>(solver: PreparedSolver<'a>, part: usize) -> SolverResult {
@@ -75,28 +441,252 @@ fn run_solver<'a>(solver: PreparedSolver<'a>, part: usize) -> SolverResult {
     }
 }
 
-pub(super) fn solve<P: AsRef<Path>>(path: P, day: usize, part: usize) -> SolverResult {
-    prepare_solver(path, day, |s| run_solver(s, part))
+/// Runs `f` over the prepared solver and measures the parsing and solving phases
+fn measure_solver<Fn: FnOnce(PreparedSolver) -> SolverResult>(
+    solver: PreparedSolver,
+    f: Fn,
+) -> (SolverResult, Metrics) {
+    let mut metrics = Metrics::default();
+
+    PARSED.with(|p| p.set(None));
+    let start = Instant::now();
+    let (result, memory) = alloc::measure(|| catch_panic(|| f(solver)));
+    let end = Instant::now();
+
+    let parsed = PARSED.with(Cell::take).map(|p| p.clamp(start, end));
+    metrics.parse = parsed.map(|p| p - start);
+    metrics.solve = end - parsed.unwrap_or(start);
+    metrics.memory = memory;
+
+    (result, metrics)
 }
 
-fn run_test<'a>(solver: PreparedSolver<'a>, part: usize) -> SolverResult {
-    let expected = solver.1.test_expected(part);
-    let result = if part == 1 {
-        solver.1.solve_part1(solver.0)
-    } else {
-        solver.1.solve_part2(solver.0)
-    }?;
+/// Runs `f` over the prepared solver on a thread of its own, so that a solver going over its
+/// `timeout` can be reported while it is left to finish in the background
+fn watch_solver<Fn>(
+    solver: PreparedSolver<'static>,
+    timeout: Option<Duration>,
+    f: Fn,
+) -> (SolverResult, Metrics)
+where
+    Fn: FnOnce(PreparedSolver) -> SolverResult + Send + 'static,
+{
+    let token = CancellationToken::default();
+    let (tx, rx) = mpsc::channel();
+
+    let start = Instant::now();
+    let handle = thread::spawn({
+        let token = token.clone();
+        move || {
+            CANCELLATION.set(Some(token));
+
+            // Allocations are counted per thread, so when they are the solver consumes a copy of
+            // the input made on its own thread, as benchmarks do, and the input is handed back to
+            // be freed by the thread that read it
+            let PreparedSolver(lines, solver) = solver;
+            let (lines, input) = if alloc::COUNTED {
+                (lines.clone(), lines)
+            } else {
+                (lines, Vec::new())
+            };
+
+            let _ = tx.send((measure_solver(PreparedSolver(lines, solver), f), input));
+        }
+    });
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    match received {
+        Ok((measured, _input)) => measured,
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+
+            let elapsed = start.elapsed();
+            let metrics = Metrics {
+                solve: elapsed,
+                ..Metrics::default()
+            };
+
+            (Err(SolverError::Timeout(elapsed)), metrics)
+        }
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("solver thread exited without a result"),
+        },
+    }
+}
+
+/// Runs `f` over the prepared solver of a day and measures it, giving up after `timeout`
+fn measured<Fn>(
+    input: Input,
+    day: usize,
+    timeout: Option<Duration>,
+    f: Fn,
+) -> (SolverResult, Metrics)
+where
+    Fn: FnOnce(PreparedSolver) -> SolverResult + Send + 'static,
+{
+    let start = Instant::now();
+    let mut read = Duration::ZERO;
+
+    let result = input.open().and_then(|reader| {
+        prepare_solver(reader, input.path(), day, |s| {
+            read = start.elapsed();
+            let (result, metrics) = watch_solver(s, timeout, f);
+            Ok((result.map_err(|e| e.in_file(input.path())), metrics))
+        })
+    });
+
+    match result {
+        Ok((result, metrics)) => (result, Metrics { read, ..metrics }),
+        Err(e) => (
+            Err(e),
+            Metrics {
+                read: start.elapsed(),
+                ..Metrics::default()
+            },
+        ),
+    }
+}
+
+pub(super) fn solve(
+    input: Input,
+    day: usize,
+    part: usize,
+    timeout: Option<Duration>,
+) -> (SolverResult, Metrics) {
+    measured(input, day, timeout, move |s| run_solver(s, part))
+}
+
+/// Extension of the file holding the answer of the test fixture it is named after, as in
+/// `day1.part1.test2.expected` for `day1.part1.test2.txt`
+const EXPECTED_EXTENSION: &str = "expected";
+
+/// Prefix of the first line of a test fixture, if it holds the answer of the fixture itself
+const EXPECTED_HEADER: &str = "# expected:";
+
+/// The sidecar file holding the answer of the test fixture at `path`, if it has one
+pub(super) fn sidecar(path: &Path) -> PathBuf {
+    path.with_extension(EXPECTED_EXTENSION)
+}
+
+/// Reads the answer of a test fixture from its sidecar file, if it has one
+fn read_sidecar(input: Input) -> Result<Option<String>, SolverError> {
+    let Input::File(path) = input else {
+        return Ok(None);
+    };
+
+    let sidecar = sidecar(path);
+    match fs::read_to_string(&sidecar) {
+        Ok(expected) => Ok(Some(expected.trim().to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(SolverError::InputFile(sidecar, e)),
+    }
+}
+
+/// Answer of a test fixture from its sidecar file or else its header line, if it has either
+pub(super) fn fixture_expected(input: Input) -> Result<Option<String>, SolverError> {
+    if let Some(expected) = read_sidecar(input)? {
+        return Ok(Some(expected));
+    }
+
+    let mut first = String::new();
+    input
+        .open()?
+        .read_line(&mut first)
+        .map_err(|e| SolverError::InputFile(input.path().to_path_buf(), e))?;
+
+    Ok(first
+        .strip_prefix(EXPECTED_HEADER)
+        .map(|expected| expected.trim().to_string()))
+}
+
+/// Checks the answer of a test fixture against the `expected` one or else the answer of
+/// [`Solver::test_expected`], leaving out the header line of the fixture
+fn run_test(solver: PreparedSolver, part: usize, expected: Option<String>) -> SolverResult {
+    let PreparedSolver(mut lines, solver) = solver;
+
+    if lines
+        .first()
+        .is_some_and(|l| l.starts_with(EXPECTED_HEADER))
+    {
+        lines.remove(0);
+    }
+
+    let expected = expected.unwrap_or_else(|| solver.test_expected(part).to_string());
+    let result = run_solver(PreparedSolver(lines, solver), part)?;
 
     if result == expected {
         Ok(result)
     } else {
         Err(SolverError::Test {
             got: result,
-            expected: expected.to_string(),
+            expected,
         })
     }
 }
 
-pub(super) fn test<P: AsRef<Path>>(path: P, day: usize, part: usize) -> SolverResult {
-    prepare_solver(path, day, |s| run_test(s, part))
+/// Tests a solver against an example, whose answer is `expected` or else the one of its solver
+pub(super) fn test(
+    input: Input,
+    day: usize,
+    part: usize,
+    expected: Option<&str>,
+    timeout: Option<Duration>,
+) -> (SolverResult, Metrics) {
+    let expected = expected.map(String::from);
+    measured(input, day, timeout, move |s| run_test(s, part, expected))
+}
+
+/// Samples of a benchmark, along with the heap usage of its last iteration
+#[derive(Debug)]
+pub(super) struct Bench {
+    pub(super) samples: Vec<Duration>,
+    pub(super) memory: Option<alloc::Stats>,
+}
+
+fn run_bench(
+    solver: PreparedSolver,
+    part: usize,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Bench, SolverError> {
+    let PreparedSolver(lines, solver) = solver;
+    let mut bench = Bench {
+        samples: Vec::with_capacity(iterations),
+        memory: None,
+    };
+
+    for i in 0..warmup + iterations {
+        // Each run consumes its own copy of the input, clone it before starting the clock
+        let lines = lines.clone();
+
+        let start = Instant::now();
+        let (result, memory) =
+            alloc::measure(|| catch_panic(|| run_solver(PreparedSolver(lines, solver), part)));
+        let elapsed = start.elapsed();
+        result?;
+
+        if i >= warmup {
+            bench.samples.push(elapsed);
+            bench.memory = memory;
+        }
+    }
+
+    Ok(bench)
+}
+
+pub(super) fn bench(
+    input: Input,
+    day: usize,
+    part: usize,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Bench, SolverError> {
+    prepare_solver(input.open()?, input.path(), day, |s| {
+        run_bench(s, part, warmup, iterations)
+    })
 }
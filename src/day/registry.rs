@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use super::Solver;
+
+/// A solver along with where it belongs in the calendar
+pub(crate) struct Entry {
+    pub(crate) year: u16,
+    pub(crate) day: usize,
+    pub(crate) module: &'static str,
+    pub(crate) solver: Box<dyn Solver>,
+}
+
+impl Entry {
+    pub(crate) fn name(&self) -> &'static str {
+        self.solver.name()
+    }
+}
+
+pub(crate) struct Registry {
+    entries: BTreeMap<(u16, usize), Entry>,
+}
+
+impl Registry {
+    pub(super) fn new(entries: Vec<Entry>) -> Self {
+        let mut registry = Self {
+            entries: BTreeMap::new(),
+        };
+
+        for entry in entries {
+            let key = (entry.year, entry.day);
+            if let Some(previous) = registry.entries.insert(key, entry) {
+                panic!(
+                    "{} day {} is registered by both {} and {}",
+                    key.0, key.1, previous.module, registry.entries[&key].module
+                );
+            }
+        }
+
+        registry
+    }
+
+    pub(crate) fn get(&self, year: u16, day: usize) -> Option<&Entry> {
+        self.entries.get(&(year, day))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.values()
+    }
+}
+
+/// Declares the day modules and registers their solver, each day appearing exactly once:
+///
+/// ```ignore
+/// solvers! {
+///     2022 => {
+///         1 => day1,
+///         2 => day2,
+///     }
+/// }
+/// ```
+macro_rules! solvers {
+    ($($year:literal => { $($day:literal => $module:ident),* $(,)? })*) => {
+        $($(mod $module;)*)*
+
+        fn entries() -> Vec<registry::Entry> {
+            vec![$($(registry::Entry {
+                year: $year,
+                day: $day,
+                module: stringify!($module),
+                solver: $module::new(),
+            },)*)*]
+        }
+    };
+}
+
+pub(super) use solvers;
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::report::{Record, Status, Tally};
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn seconds(d: Duration) -> String {
+    format!("{:.6}", d.as_secs_f64())
+}
+
+fn attributes<'a>(records: impl IntoIterator<Item = &'a Record>) -> String {
+    let mut tally = Tally::default();
+    for record in records {
+        tally.add(record);
+    }
+
+    format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\"",
+        tally.total(),
+        tally.failed,
+        tally.errors + tally.timeouts,
+        seconds(tally.elapsed)
+    )
+}
+
+fn write_testcase(xml: &mut String, r: &Record) -> std::fmt::Result {
+    let file_name = r
+        .input_file
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    write!(
+        xml,
+        "    <testcase name=\"Part {} [{}]\" classname=\"day{}.part{}\" file=\"{}\" time=\"{}\"",
+        r.part,
+        escape(&file_name),
+        r.day,
+        r.part,
+        escape(&r.input_file.display().to_string()),
+        seconds(r.elapsed)
+    )?;
+
+    let got = r.answer.as_deref().unwrap_or_default();
+    let expected = r.expected.as_deref().unwrap_or_default();
+    let error = r.error.as_deref().unwrap_or_default();
+
+    match r.status {
+        Status::Ok => writeln!(xml, "/>"),
+        Status::Failed => {
+            writeln!(xml, ">")?;
+            writeln!(
+                xml,
+                "      <failure message=\"expected {}, got {}\" type=\"Test\">got: {}\nexpected: {}</failure>",
+                escape(expected),
+                escape(got),
+                escape(got),
+                escape(expected)
+            )?;
+            writeln!(xml, "    </testcase>")
+        }
+        Status::Error => {
+            writeln!(xml, ">")?;
+            writeln!(
+                xml,
+                "      <error message=\"{}\" type=\"SolverError\">{}</error>",
+                escape(error),
+                escape(error)
+            )?;
+            writeln!(xml, "    </testcase>")
+        }
+        Status::Timeout => {
+            writeln!(xml, ">")?;
+            writeln!(
+                xml,
+                "      <error message=\"{}\" type=\"Timeout\">{}</error>",
+                escape(error),
+                escape(error)
+            )?;
+            writeln!(xml, "    </testcase>")
+        }
+    }
+}
+
+fn write_report(xml: &mut String, records: &[Record]) -> std::fmt::Result {
+    let mut days: BTreeMap<usize, Vec<&Record>> = BTreeMap::new();
+    for record in records {
+        days.entry(record.day).or_default().push(record);
+    }
+
+    writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(xml, "<testsuites name=\"aoc2k22\" {}>", attributes(records))?;
+
+    for (day, records) in &days {
+        let name = records.first().map(|r| r.name).unwrap_or("Unknown");
+
+        writeln!(
+            xml,
+            "  <testsuite name=\"Day {} ({})\" {}>",
+            day,
+            escape(name),
+            attributes(records.iter().copied())
+        )?;
+
+        for record in records {
+            write_testcase(xml, record)?;
+        }
+
+        writeln!(xml, "  </testsuite>")?;
+    }
+
+    writeln!(xml, "</testsuites>")
+}
+
+/// Renders test records as a JUnit XML report, with one testsuite per day
+pub(super) fn render(records: &[Record]) -> String {
+    let mut xml = String::new();
+    write_report(&mut xml, records).expect("writing to a String should not fail");
+    xml
+}
+
+pub(super) fn write<P: AsRef<Path>>(path: P, records: &[Record]) -> io::Result<()> {
+    fs::write(path, render(records))
+}
@@ -1,12 +1,103 @@
+mod alloc;
+mod answers;
+mod bench;
+mod cli;
+mod client;
 mod cmd;
+mod config;
 mod day;
+mod junit;
+mod pool;
+mod report;
+mod scaffold;
+mod submission;
+mod watch;
 
-use cmd::Command;
+use std::process::ExitCode;
 
-const INPUT_PREFIX: &'static str = "inputs";
+use cmd::{Command, Error};
+use report::Summary;
 
-fn main() {
-    Command::parse_from_args()
-        .and_then(|c| c.run(INPUT_PREFIX))
-        .expect("Failed to handle command");
+/// Exit code when at least one test failed or errored
+const EXIT_TEST_FAILURE: u8 = 1;
+
+/// Exit code when the command line is invalid
+const EXIT_USAGE_ERROR: u8 = 2;
+
+/// Exit code when a solver failed outside of a test run
+const EXIT_SOLVER_ERROR: u8 = 3;
+
+/// Exit code when the input files could not be found, read or written
+const EXIT_INPUT_ERROR: u8 = 4;
+
+/// Exit code when the config file or one of the data files next to the inputs is broken
+const EXIT_CONFIG_ERROR: u8 = 5;
+
+/// Exit code when talking to the website failed
+const EXIT_CLIENT_ERROR: u8 = 6;
+
+/// Exit code when an answer was not submitted, as it would have been rejected anyway
+const EXIT_SUBMISSION_REFUSED: u8 = 7;
+
+fn exit_code(e: &Error) -> u8 {
+    match e {
+        Error::Usage(..) => EXIT_USAGE_ERROR,
+        Error::Solver(_, day::SolverError::InputFile(..)) => EXIT_INPUT_ERROR,
+        Error::Solver(..) => EXIT_SOLVER_ERROR,
+        Error::ResolvePath(_)
+        | Error::CurrentDirectory(_)
+        | Error::ReadInputDirectory(..)
+        | Error::ReadStdin(_)
+        | Error::WriteReport(..)
+        | Error::WriteInput(..)
+        | Error::AmbiguousInput(_) => EXIT_INPUT_ERROR,
+        Error::Config(_) | Error::Scaffold(_) | Error::Submission(_) | Error::Answers(_) => {
+            EXIT_CONFIG_ERROR
+        }
+        Error::Client(_) => EXIT_CLIENT_ERROR,
+        Error::ImpossibleAnswer(..) | Error::RateLimited(_) => EXIT_SUBMISSION_REFUSED,
+    }
+}
+
+/// Exit code of a run whose failures were already reported along with their records
+fn failure_code(summary: &Summary) -> u8 {
+    match summary.kind {
+        Some(report::Kind::Test | report::Kind::Verify) => EXIT_TEST_FAILURE,
+        _ if summary.input_errors > 0 => EXIT_INPUT_ERROR,
+        _ => EXIT_SOLVER_ERROR,
+    }
+}
+
+/// Prints the chain of errors that led to `e`, one per line
+fn print_causes(e: &dyn std::error::Error) {
+    let mut source = e.source();
+    while let Some(cause) = source {
+        eprintln!("  caused by: {}", cause);
+        source = cause.source();
+    }
+}
+
+fn main() -> ExitCode {
+    match Command::parse_from_args().and_then(|c| c.run()) {
+        Ok(summary) if summary.is_success() => ExitCode::SUCCESS,
+        Ok(summary) => ExitCode::from(failure_code(&summary)),
+        Err(Error::Usage(command, e)) => {
+            eprintln!("error: {}", e);
+            print_causes(&e);
+            eprintln!("\n{}", cli::hint(command.as_deref()));
+            ExitCode::from(EXIT_USAGE_ERROR)
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            print_causes(&e);
+
+            if let Error::Solver(_, e) = &e {
+                if let Some(snippet) = e.snippet() {
+                    eprint!("{}", snippet);
+                }
+            }
+
+            ExitCode::from(exit_code(&e))
+        }
+    }
 }
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `work` over every item on a pool of `workers` threads and hands each result to
+/// `emit` in the original order of `items`, as soon as all the preceding results are in.
+///
+/// Breaking from `emit` stops the pool from picking up new items, the function then returns
+/// once the in-flight items are done.
+pub(super) fn run_ordered<T, R, W, F>(items: &[T], workers: usize, work: W, mut emit: F)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    F: FnMut(&T, R) -> ControlFlow<()>,
+{
+    let workers = workers.clamp(1, items.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        let (tx, rx) = mpsc::channel();
+
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, stop, work) = (&next, &stop, &work);
+
+            s.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+
+                    if tx.send((index, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut current = 0;
+
+        for (index, result) in rx {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&current) {
+                if emit(&items[current], result).is_break() {
+                    stop.store(true, Ordering::Relaxed);
+                    return;
+                }
+
+                current += 1;
+            }
+        }
+    })
+}
@@ -0,0 +1,533 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::alloc;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Kind {
+    Solve,
+    Test,
+    Verify,
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Solve => "solve",
+            Kind::Test => "test",
+            Kind::Verify => "verify",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Status {
+    Ok,
+    Failed,
+    Error,
+    Timeout,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Failed => "failed",
+            Status::Error => "error",
+            Status::Timeout => "timeout",
+        }
+    }
+}
+
+/// The outcome of running a solver over a single input file
+#[derive(Debug, Serialize)]
+pub(super) struct Record {
+    pub(super) kind: Kind,
+
+    pub(super) day: usize,
+    pub(super) part: usize,
+    pub(super) name: &'static str,
+    pub(super) input_file: PathBuf,
+
+    pub(super) status: Status,
+    pub(super) answer: Option<String>,
+    pub(super) expected: Option<String>,
+    pub(super) error: Option<String>,
+
+    /// Where the input could not be parsed, rendered for a terminal
+    #[serde(skip)]
+    pub(super) snippet: Option<String>,
+
+    /// Reasons to doubt the answer, from the feedback to previous submissions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(super) warnings: Vec<String>,
+
+    #[serde(rename = "elapsed_ms", serialize_with = "serialize_millis")]
+    pub(super) elapsed: Duration,
+
+    /// Time spent reading, parsing and solving, parsing being unknown unless the solver marks it
+    #[serde(rename = "read_ms", serialize_with = "serialize_millis")]
+    pub(super) read: Duration,
+    #[serde(rename = "parse_ms", serialize_with = "serialize_optional_millis")]
+    pub(super) parse: Option<Duration>,
+    #[serde(rename = "solve_ms", serialize_with = "serialize_millis")]
+    pub(super) solve: Duration,
+
+    /// Heap usage of the solver, only known with the `alloc-stats` feature
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub(super) memory: Option<alloc::Stats>,
+}
+
+impl Record {
+    /// Which puzzle and input file the record is about
+    pub(super) fn title(&self) -> String {
+        format!(
+            "Day {} ({}) - Part {} [{:?}]",
+            self.day, self.name, self.part, self.input_file
+        )
+    }
+
+    /// Total time followed by the time of each phase and the heap usage, if known
+    pub(super) fn timings(&self) -> String {
+        let timings = match self.parse {
+            Some(parse) => format!(
+                "{:?}: read {:?}, parse {:?}, solve {:?}",
+                self.elapsed, self.read, parse, self.solve
+            ),
+            None => format!(
+                "{:?}: read {:?}, solve {:?}",
+                self.elapsed, self.read, self.solve
+            ),
+        };
+
+        match self.memory {
+            Some(memory) => format!("{}; {}", timings, memory),
+            None => timings,
+        }
+    }
+
+    /// Short description of the outcome, without timings so that two runs can be compared
+    pub(super) fn outcome(&self) -> String {
+        let mut outcome = match self.status {
+            Status::Ok => format!("[OK] {}", self.answer.as_deref().unwrap_or_default()),
+            Status::Failed => format!(
+                "[FAILED] expected {}, got {}",
+                self.expected.as_deref().unwrap_or_default(),
+                self.answer.as_deref().unwrap_or_default()
+            ),
+            Status::Error => format!("[ERROR] {}", self.error.as_deref().unwrap_or_default()),
+            Status::Timeout => "[TIMEOUT]".to_string(),
+        };
+
+        for warning in &self.warnings {
+            outcome.push_str(&format!(" (WARN {})", warning));
+        }
+
+        outcome
+    }
+}
+
+fn serialize_millis<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(d.as_secs_f64() * 1e3)
+}
+
+fn serialize_optional_millis<S: Serializer>(d: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+    match d {
+        Some(d) => serialize_millis(d, s),
+        None => s.serialize_none(),
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub(super) struct Tally {
+    pub(super) passed: usize,
+    pub(super) failed: usize,
+    pub(super) errors: usize,
+    pub(super) timeouts: usize,
+    pub(super) elapsed: Duration,
+}
+
+impl Tally {
+    pub(super) fn add(&mut self, record: &Record) {
+        match record.status {
+            Status::Ok => self.passed += 1,
+            Status::Failed => self.failed += 1,
+            Status::Error => self.errors += 1,
+            Status::Timeout => self.timeouts += 1,
+        }
+
+        self.elapsed += record.elapsed;
+    }
+
+    pub(super) fn total(&self) -> usize {
+        self.passed + self.failed + self.errors + self.timeouts
+    }
+}
+
+/// Pass, failure and error counts of a whole run, broken down by day
+#[derive(Debug, Default)]
+pub(super) struct Summary {
+    /// What the records were about, unknown for commands that do not report any
+    pub(super) kind: Option<Kind>,
+
+    pub(super) days: BTreeMap<usize, (&'static str, Tally)>,
+    pub(super) total: Tally,
+    pub(super) skipped: usize,
+
+    /// How many of the errors were about reading the input rather than solving it
+    pub(super) input_errors: usize,
+}
+
+impl Summary {
+    pub(super) fn add(&mut self, record: &Record) {
+        self.days
+            .entry(record.day)
+            .or_insert((record.name, Tally::default()))
+            .1
+            .add(record);
+
+        self.total.add(record);
+    }
+
+    pub(super) fn is_success(&self) -> bool {
+        self.total.failed == 0 && self.total.errors == 0 && self.total.timeouts == 0
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Format {
+    Text,
+    Json,
+    Jsonl,
+    Csv,
+    Tap,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            "csv" => Ok(Self::Csv),
+            "tap" => Ok(Self::Tap),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+pub(super) trait Reporter {
+    fn begin(&mut self, _total: usize) {}
+
+    fn record(&mut self, record: &Record);
+
+    fn summary(&mut self, _summary: &Summary) {}
+
+    fn finish(&mut self) {}
+}
+
+struct Text;
+
+impl Reporter for Text {
+    fn record(&mut self, r: &Record) {
+        match (r.kind, r.status) {
+            (kind, Status::Timeout) => println!(
+                "{} {}   [TIMEOUT]  ({})",
+                match kind {
+                    Kind::Solve => "Failed",
+                    Kind::Test => "Test -",
+                    Kind::Verify => "Verify -",
+                },
+                r.title(),
+                r.error.as_deref().unwrap_or_default()
+            ),
+            (Kind::Solve, Status::Ok) => println!(
+                "Solved Day {} ({}) - Part {} [{:?}] -> {}   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.answer.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+            (Kind::Solve, _) => println!(
+                "Failed Day {} ({}) - Part {} [{:?}] -> {}   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.error.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+            (Kind::Test, Status::Ok) => println!(
+                "Test - Day {} ({}) - Part {} [{:?}]   [OK]  ({})   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.answer.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+            (Kind::Verify, Status::Ok) => println!(
+                "Verify - Day {} ({}) - Part {} [{:?}]   [OK]  ({})   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.answer.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+            (Kind::Test, _) => println!(
+                "Test - Day {} ({}) - Part {} [{:?}]   [FAILED]  ({})   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.error.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+            (Kind::Verify, Status::Failed) => println!(
+                "Verify - Day {} ({}) - Part {} [{:?}]   [MISMATCH]  (expected {}, got {})   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.expected.as_deref().unwrap_or_default(),
+                r.answer.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+            (Kind::Verify, _) => println!(
+                "Verify - Day {} ({}) - Part {} [{:?}]   [FAILED]  ({})   [{}]",
+                r.day,
+                r.name,
+                r.part,
+                r.input_file,
+                r.error.as_deref().unwrap_or_default(),
+                r.timings()
+            ),
+        }
+
+        if let Some(snippet) = &r.snippet {
+            print!("{}", snippet);
+        }
+
+        for warning in &r.warnings {
+            println!("WARN {}", warning);
+        }
+    }
+
+    fn summary(&mut self, summary: &Summary) {
+        println!();
+        println!(
+            "{:<34} {:>7} {:>7} {:>7} {:>8}   {:>12}",
+            "Day", "Passed", "Failed", "Errors", "Timeouts", "Time"
+        );
+
+        for (day, (name, tally)) in &summary.days {
+            println!(
+                "{:<34} {:>7} {:>7} {:>7} {:>8}   {:>12}",
+                format!("{} ({})", day, name),
+                tally.passed,
+                tally.failed,
+                tally.errors,
+                tally.timeouts,
+                format!("{:.2?}", tally.elapsed)
+            );
+        }
+
+        println!(
+            "{:<34} {:>7} {:>7} {:>7} {:>8}   {:>12}",
+            "Total",
+            summary.total.passed,
+            summary.total.failed,
+            summary.total.errors,
+            summary.total.timeouts,
+            format!("{:.2?}", summary.total.elapsed)
+        );
+
+        if summary.skipped > 0 {
+            println!("Stopped after first failure, {} skipped", summary.skipped);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Json {
+    count: usize,
+}
+
+impl Reporter for Json {
+    fn begin(&mut self, _total: usize) {
+        println!("[");
+    }
+
+    fn record(&mut self, record: &Record) {
+        if self.count > 0 {
+            println!(",");
+        }
+
+        print!("  {}", to_json(record));
+        self.count += 1;
+    }
+
+    fn finish(&mut self) {
+        if self.count > 0 {
+            println!();
+        }
+
+        println!("]");
+    }
+}
+
+struct Jsonl;
+
+impl Reporter for Jsonl {
+    fn record(&mut self, record: &Record) {
+        println!("{}", to_json(record));
+    }
+}
+
+fn to_json(record: &Record) -> String {
+    serde_json::to_string(record).expect("records should always be serializable")
+}
+
+struct Csv;
+
+impl Reporter for Csv {
+    fn begin(&mut self, _total: usize) {
+        println!(
+            "kind,day,part,name,input_file,status,answer,expected,error,warnings,elapsed_ms,read_ms,parse_ms,solve_ms,allocations,allocated_bytes,peak_bytes"
+        );
+    }
+
+    fn record(&mut self, r: &Record) {
+        let fields = [
+            r.kind.as_str().to_string(),
+            r.day.to_string(),
+            r.part.to_string(),
+            r.name.to_string(),
+            r.input_file.display().to_string(),
+            r.status.as_str().to_string(),
+            r.answer.clone().unwrap_or_default(),
+            r.expected.clone().unwrap_or_default(),
+            r.error.clone().unwrap_or_default(),
+            r.warnings.join("; "),
+            (r.elapsed.as_secs_f64() * 1e3).to_string(),
+            (r.read.as_secs_f64() * 1e3).to_string(),
+            r.parse
+                .map(|p| (p.as_secs_f64() * 1e3).to_string())
+                .unwrap_or_default(),
+            (r.solve.as_secs_f64() * 1e3).to_string(),
+            r.memory
+                .map(|m| m.allocations.to_string())
+                .unwrap_or_default(),
+            r.memory.map(|m| m.bytes.to_string()).unwrap_or_default(),
+            r.memory.map(|m| m.peak.to_string()).unwrap_or_default(),
+        ];
+
+        let fields: Vec<_> = fields.iter().map(|f| csv_field(f)).collect();
+        println!("{}", fields.join(","));
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[derive(Default)]
+struct Tap {
+    count: usize,
+}
+
+impl Reporter for Tap {
+    fn begin(&mut self, total: usize) {
+        println!("TAP version 13");
+        println!("1..{}", total);
+    }
+
+    fn record(&mut self, r: &Record) {
+        self.count += 1;
+
+        let ok = if r.status == Status::Ok {
+            "ok"
+        } else {
+            "not ok"
+        };
+        println!(
+            "{} {} - Day {} ({}) - Part {} [{}]",
+            ok,
+            self.count,
+            r.day,
+            r.name,
+            r.part,
+            r.input_file.display()
+        );
+
+        println!("  ---");
+        println!("  status: {}", r.status.as_str());
+        if let Some(answer) = &r.answer {
+            println!("  answer: {:?}", answer);
+        }
+        if let Some(expected) = &r.expected {
+            println!("  expected: {:?}", expected);
+        }
+        if let Some(error) = &r.error {
+            println!("  error: {:?}", error);
+        }
+        if !r.warnings.is_empty() {
+            println!("  warnings:");
+            for warning in &r.warnings {
+                println!("    - {:?}", warning);
+            }
+        }
+        println!("  elapsed_ms: {}", r.elapsed.as_secs_f64() * 1e3);
+        println!("  read_ms: {}", r.read.as_secs_f64() * 1e3);
+        if let Some(parse) = r.parse {
+            println!("  parse_ms: {}", parse.as_secs_f64() * 1e3);
+        }
+        println!("  solve_ms: {}", r.solve.as_secs_f64() * 1e3);
+        if let Some(memory) = r.memory {
+            println!("  allocations: {}", memory.allocations);
+            println!("  allocated_bytes: {}", memory.bytes);
+            println!("  peak_bytes: {}", memory.peak);
+        }
+        println!("  ...");
+    }
+
+    fn summary(&mut self, summary: &Summary) {
+        if summary.skipped > 0 {
+            println!("Bail out! Stopped after first failure");
+        }
+
+        println!(
+            "# passed {}, failed {}, errors {}, timeouts {}",
+            summary.total.passed,
+            summary.total.failed,
+            summary.total.errors,
+            summary.total.timeouts
+        );
+    }
+}
+
+pub(super) fn new(format: Format) -> Box<dyn Reporter> {
+    match format {
+        Format::Text => Box::new(Text),
+        Format::Json => Box::<Json>::default(),
+        Format::Jsonl => Box::new(Jsonl),
+        Format::Csv => Box::new(Csv),
+        Format::Tap => Box::<Tap>::default(),
+    }
+}
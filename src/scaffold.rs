@@ -0,0 +1,175 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::day;
+
+#[derive(Debug, Error)]
+pub(super) enum Error {
+    #[error("day {0} is already registered")]
+    AlreadyRegistered(usize),
+
+    #[error("{0:?} already exists, refusing to overwrite it")]
+    Exists(PathBuf),
+
+    #[error("could not find the solvers! block for {1} in {0:?}")]
+    MissingRegistry(PathBuf, u16),
+
+    #[error("failed to write {0:?}")]
+    Io(PathBuf, #[source] io::Error),
+}
+
+const TEMPLATE: &str = r#"use thiserror::Error;
+
+use super::{Solver, SolverError};
+
+#[derive(Debug, Error)]
+enum Error {
+    #[error("part {0} is not solved yet")]
+    Unsolved(usize),
+}
+
+struct Day{day};
+
+impl Solver for Day{day} {
+    fn name(&self) -> &'static str {
+        "{name}"
+    }
+
+    fn solve_part1(&self, _lines: Vec<String>) -> super::SolverResult {
+        Err(SolverError::Generic(Error::Unsolved(1).into()))
+    }
+
+    fn solve_part2(&self, _lines: Vec<String>) -> super::SolverResult {
+        Err(SolverError::Generic(Error::Unsolved(2).into()))
+    }
+
+    fn test_expected(&self, part: usize) -> &'static str {
+        match part {
+            1 => "",
+            2 => "",
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub(super) fn new() -> Box<dyn Solver> {
+    Box::new(Day{day})
+}
+"#;
+
+fn render(day: usize, name: &str) -> String {
+    TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{name}", &name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Adds `day => module,` to the block of `year` in the `solvers!` invocation, keeping the days
+/// sorted
+fn register(source: &str, year: u16, day: usize, module: &str) -> Option<String> {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+
+    let macro_start = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("registry::solvers!"))?;
+
+    let year_start = macro_start
+        + lines[macro_start..]
+            .iter()
+            .position(|l| l.trim() == format!("{} => {{", year))?;
+
+    let year_end = year_start + lines[year_start..].iter().position(|l| l.trim() == "}")?;
+
+    let position = lines[year_start + 1..year_end]
+        .iter()
+        .position(|l| {
+            l.split_once("=>")
+                .and_then(|(d, _)| d.trim().parse::<usize>().ok())
+                .is_some_and(|d| d > day)
+        })
+        .map(|p| year_start + 1 + p)
+        .unwrap_or(year_end);
+
+    lines.insert(position, format!("        {} => {},", day, module));
+
+    let mut registered = lines.join("\n");
+    if source.ends_with('\n') {
+        registered.push('\n');
+    }
+
+    Some(registered)
+}
+
+fn create(path: &Path, contents: &str) -> Result<(), Error> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => Error::Exists(path.to_path_buf()),
+            _ => Error::Io(path.to_path_buf(), e),
+        })?;
+
+    file.write_all(contents.as_bytes())
+        .map_err(|e| Error::Io(path.to_path_buf(), e))
+}
+
+/// Generates the solver module of a new day, registers it and creates empty input files for it.
+///
+/// Returns the list of files that were created or modified.
+pub(super) fn new_day(
+    source_dir: &Path,
+    input_dir: &Path,
+    day: usize,
+    name: &str,
+) -> Result<Vec<PathBuf>, Error> {
+    if day::registry().get(day::YEAR, day).is_some() {
+        return Err(Error::AlreadyRegistered(day));
+    }
+
+    let module = format!("day{}", day);
+    let module_path = source_dir.join(format!("{}.rs", module));
+    let registry_path = source_dir.join("mod.rs");
+
+    let test_files: Vec<PathBuf> = day::PARTS
+        .iter()
+        .map(|part| input_dir.join(format!("day{}.part{}.test.txt", day, part)))
+        .collect();
+
+    // The input is shared by both parts, named the way `fetch` caches it. It may have been
+    // fetched already, in which case it is kept as is
+    let input_file = input_dir.join(format!("day{}.input.txt", day));
+
+    // Check everything upfront to avoid leaving a half-generated day behind
+    if let Some(existing) = std::iter::once(&module_path)
+        .chain(&test_files)
+        .find(|p| p.exists())
+    {
+        return Err(Error::Exists(existing.to_path_buf()));
+    }
+
+    let mut input_files = test_files;
+    if !input_file.exists() {
+        input_files.push(input_file);
+    }
+
+    let source =
+        fs::read_to_string(&registry_path).map_err(|e| Error::Io(registry_path.clone(), e))?;
+    let registered = register(&source, day::YEAR, day, &module)
+        .ok_or(Error::MissingRegistry(registry_path.clone(), day::YEAR))?;
+
+    create(&module_path, &render(day, name))?;
+    fs::write(&registry_path, registered).map_err(|e| Error::Io(registry_path.clone(), e))?;
+
+    fs::create_dir_all(input_dir).map_err(|e| Error::Io(input_dir.to_path_buf(), e))?;
+    for input_file in &input_files {
+        create(input_file, "")?;
+    }
+
+    let mut created = vec![module_path, registry_path];
+    created.extend(input_files);
+
+    Ok(created)
+}
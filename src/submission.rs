@@ -0,0 +1,346 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Name of the database file, relative to the input directory
+pub(super) const DATABASE_FILE: &str = "submissions.toml";
+
+#[derive(Debug, Error)]
+pub(super) enum Error {
+    #[error("failed to read {0:?}")]
+    Read(PathBuf, #[source] io::Error),
+
+    #[error("failed to parse {0:?}")]
+    Parse(PathBuf, #[source] toml::de::Error),
+
+    #[error("failed to write {0:?}")]
+    Write(PathBuf, #[source] io::Error),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+    Unknown,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::RateLimited => "rate limited",
+            Outcome::AlreadySolved => "already solved",
+            Outcome::Unknown => "unknown",
+        };
+
+        f.write_str(s)
+    }
+}
+
+/// What the website had to say about a submitted answer
+#[derive(Debug)]
+pub(super) struct Feedback {
+    pub(super) outcome: Outcome,
+    pub(super) wait: Option<Duration>,
+    pub(super) message: String,
+}
+
+impl Feedback {
+    pub(super) fn parse(html: &str) -> Self {
+        let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+        let tags = Regex::new(r"<[^>]*>").unwrap();
+        let spaces = Regex::new(r"\s+").unwrap();
+
+        let body = article
+            .captures(html)
+            .and_then(|c| c.get(1))
+            .map_or(html, |m| m.as_str());
+
+        let message = tags.replace_all(body, "");
+        let message = spaces.replace_all(message.trim(), " ").to_string();
+
+        let text = message.to_lowercase();
+        let outcome = if text.contains("that's the right answer") {
+            Outcome::Correct
+        } else if text.contains("you gave an answer too recently") {
+            Outcome::RateLimited
+        } else if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if text.contains("that's not the right answer") {
+            Outcome::Wrong
+        } else if text.contains("you don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        };
+
+        let left = Regex::new(r"you have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let penalty = Regex::new(r"please wait (\w+) minutes? before trying again").unwrap();
+
+        let wait = left
+            .captures(&text)
+            .map(|c| {
+                let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds: u64 = c[2].parse().unwrap_or(0);
+                Duration::from_secs(minutes * 60 + seconds)
+            })
+            .or_else(|| {
+                penalty
+                    .captures(&text)
+                    .and_then(|c| parse_minutes(&c[1]))
+                    .map(|m| Duration::from_secs(m * 60))
+            });
+
+        Self {
+            outcome,
+            wait,
+            message,
+        }
+    }
+}
+
+fn parse_minutes(s: &str) -> Option<u64> {
+    const WORDS: &[&str] = &[
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+
+    s.parse()
+        .ok()
+        .or_else(|| WORDS.iter().position(|&w| w == s).map(|i| i as u64 + 1))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Submission {
+    pub(super) year: u16,
+    pub(super) day: usize,
+    pub(super) part: usize,
+    pub(super) answer: String,
+    pub(super) outcome: Outcome,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) wait_secs: Option<u64>,
+
+    /// Seconds since the Unix epoch
+    pub(super) submitted_at: u64,
+}
+
+impl Submission {
+    pub(super) fn new(
+        year: u16,
+        day: usize,
+        part: usize,
+        answer: &str,
+        feedback: &Feedback,
+    ) -> Self {
+        let submitted_at = now();
+
+        Self {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome: feedback.outcome,
+            wait_secs: feedback.wait.map(|w| w.as_secs()),
+            submitted_at,
+        }
+    }
+}
+
+/// What the feedback to previous submissions tells about the answer of a part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct PuzzleState {
+    pub(super) year: u16,
+    pub(super) day: usize,
+    pub(super) part: usize,
+
+    /// Lowest answer known to be too high
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) too_high: Option<i128>,
+
+    /// Highest answer known to be too low
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) too_low: Option<i128>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) wrong: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) accepted: Option<String>,
+}
+
+impl PuzzleState {
+    fn new(year: u16, day: usize, part: usize) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            too_high: None,
+            too_low: None,
+            wrong: Vec::new(),
+            accepted: None,
+        }
+    }
+
+    fn update(&mut self, answer: &str, outcome: Outcome) {
+        let value = answer.trim().parse::<i128>().ok();
+
+        match outcome {
+            Outcome::TooHigh => {
+                if let Some(value) = value {
+                    self.too_high = Some(self.too_high.map_or(value, |h| h.min(value)));
+                }
+            }
+            Outcome::TooLow => {
+                if let Some(value) = value {
+                    self.too_low = Some(self.too_low.map_or(value, |l| l.max(value)));
+                }
+            }
+            Outcome::Correct => self.accepted = Some(answer.to_string()),
+            _ => {}
+        }
+
+        if matches!(outcome, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+            && !self.wrong.iter().any(|w| w == answer)
+        {
+            self.wrong.push(answer.to_string());
+        }
+    }
+
+    /// Returns why `answer` can not be the right answer, if anything says so
+    pub(super) fn check(&self, answer: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.wrong.iter().any(|w| w == answer) {
+            warnings.push(format!("answer {} was already rejected", answer));
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            if let Some(too_high) = self.too_high.filter(|&h| value >= h) {
+                warnings.push(format!(
+                    "answer {} is >= known too-high {}",
+                    answer, too_high
+                ));
+            }
+
+            if let Some(too_low) = self.too_low.filter(|&l| value <= l) {
+                warnings.push(format!("answer {} is <= known too-low {}", answer, too_low));
+            }
+        }
+
+        if let Some(accepted) = self.accepted.as_ref().filter(|&a| a != answer) {
+            warnings.push(format!(
+                "answer {} differs from accepted answer {}",
+                answer, accepted
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// Local record of every answer submitted to the website and the feedback it got
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct Database {
+    #[serde(default, rename = "puzzle")]
+    pub(super) puzzles: Vec<PuzzleState>,
+
+    #[serde(default, rename = "submission")]
+    pub(super) submissions: Vec<Submission>,
+}
+
+impl Database {
+    pub(super) fn path(input_dir: &Path) -> PathBuf {
+        input_dir.join(DATABASE_FILE)
+    }
+
+    pub(super) fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                toml::from_str(&contents).map_err(|e| Error::Parse(path.to_path_buf(), e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Read(path.to_path_buf(), e)),
+        }
+    }
+
+    pub(super) fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = toml::to_string_pretty(self).expect("database should be serializable");
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::Write(path.to_path_buf(), e))?;
+        }
+
+        fs::write(path, contents).map_err(|e| Error::Write(path.to_path_buf(), e))
+    }
+
+    pub(super) fn state(&self, year: u16, day: usize, part: usize) -> Option<&PuzzleState> {
+        self.puzzles
+            .iter()
+            .find(|p| (p.year, p.day, p.part) == (year, day, part))
+    }
+
+    /// Checks an answer against the known bounds and rejected answers of its part
+    pub(super) fn check(&self, year: u16, day: usize, part: usize, answer: &str) -> Vec<String> {
+        self.state(year, day, part)
+            .map(|s| s.check(answer))
+            .unwrap_or_default()
+    }
+
+    /// Returns how long to wait before the website accepts another answer, if at all
+    pub(super) fn wait(&self) -> Option<Duration> {
+        let now = now();
+
+        self.submissions
+            .iter()
+            .filter_map(|s| s.wait_secs.map(|w| s.submitted_at + w))
+            .max()
+            .filter(|&until| until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+
+    fn state_mut(&mut self, year: u16, day: usize, part: usize) -> &mut PuzzleState {
+        let key = (year, day, part);
+        let index = match self
+            .puzzles
+            .binary_search_by_key(&key, |p| (p.year, p.day, p.part))
+        {
+            Ok(index) => index,
+            Err(index) => {
+                self.puzzles
+                    .insert(index, PuzzleState::new(year, day, part));
+                index
+            }
+        };
+
+        &mut self.puzzles[index]
+    }
+
+    pub(super) fn record(&mut self, submission: Submission) {
+        self.state_mut(submission.year, submission.day, submission.part)
+            .update(&submission.answer, submission.outcome);
+        self.submissions.push(submission);
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often watched files are polled
+pub(super) const INTERVAL: Duration = Duration::from_millis(500);
+
+/// What is compared between two polls of a file. The contents are hashed as well since the
+/// modification time of a file written twice in a row may not change
+#[derive(Debug, Eq, PartialEq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    hash: u64,
+}
+
+impl Stamp {
+    fn read(path: &Path) -> Option<Self> {
+        let contents = fs::read(path).ok()?;
+
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+
+        Some(Self {
+            modified: fs::metadata(path).and_then(|m| m.modified()).ok(),
+            hash: hasher.finish(),
+        })
+    }
+}
+
+/// Polls files for changes, without relying on the notification APIs of the OS
+#[derive(Debug, Default)]
+pub(super) struct Watcher {
+    stamps: BTreeMap<PathBuf, Option<Stamp>>,
+}
+
+impl Watcher {
+    /// Returns the files that changed since the last poll, files that were not watched before
+    /// counting as changed. Files missing from `paths` stop being watched.
+    pub(super) fn poll<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) -> Vec<PathBuf> {
+        let mut stamps = BTreeMap::new();
+        let mut changed = Vec::new();
+
+        for path in paths {
+            if stamps.contains_key(path) {
+                continue;
+            }
+
+            let stamp = Stamp::read(path);
+            if self.stamps.get(path) != Some(&stamp) {
+                changed.push(path.to_path_buf());
+            }
+
+            stamps.insert(path.to_path_buf(), stamp);
+        }
+
+        self.stamps = stamps;
+        changed
+    }
+}
+
+/// Clears the terminal and moves the cursor back to its top-left corner
+pub(super) fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    let _ = io::stdout().flush();
+}